@@ -0,0 +1 @@
+segment
\ No newline at end of file
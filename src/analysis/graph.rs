@@ -0,0 +1,291 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+use wg_internal::network::NodeId;
+
+use crate::{
+    parsed_nodes::{Node, NodeType},
+    utils::parser::Parser,
+};
+
+/// Undirected graph of a topology, where only drones forward packets
+#[derive(Debug, Clone)]
+pub(crate) struct Graph {
+    kinds: BTreeMap<NodeId, NodeType>,
+    adjacency: BTreeMap<NodeId, Vec<NodeId>>,
+    pdrs: BTreeMap<NodeId, f32>,
+}
+
+impl Graph {
+    pub(crate) fn new(parser: &Parser) -> Self {
+        let nodes = parser
+            .drones
+            .iter()
+            .map(|d| d as &dyn Node)
+            .chain(parser.clients.iter().map(|c| c as &dyn Node))
+            .chain(parser.servers.iter().map(|s| s as &dyn Node));
+
+        let mut graph = Graph {
+            kinds: BTreeMap::new(),
+            adjacency: BTreeMap::new(),
+            pdrs: parser.drones.iter().map(|d| (d.id, d.pdr)).collect(),
+        };
+        for node in nodes {
+            graph.kinds.insert(node.id(), node.node_type());
+            graph
+                .adjacency
+                .insert(node.id(), node.connected_drone_ids().clone());
+        }
+        graph
+    }
+
+    pub(crate) fn nodes(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.kinds.keys().copied()
+    }
+
+    pub(crate) fn of_kind(&self, kind: NodeType) -> Vec<NodeId> {
+        self.kinds
+            .iter()
+            .filter(|(_, k)| **k == kind)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    pub(crate) fn neighbours(&self, id: NodeId) -> &[NodeId] {
+        self.adjacency.get(&id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Packet drop rate of a drone, 0 for clients and servers
+    pub(crate) fn pdr(&self, id: NodeId) -> f64 {
+        self.pdrs.get(&id).copied().map_or(0.0, f64::from)
+    }
+
+    /// Every edge once, with the lower id first
+    pub(crate) fn edges(&self) -> Vec<(NodeId, NodeId)> {
+        self.adjacency
+            .iter()
+            .flat_map(|(a, neighbours)| neighbours.iter().map(move |b| (*a, *b)))
+            .filter(|(a, b)| a < b)
+            .collect()
+    }
+
+    /// Whether a path starting from `source` can continue after `id`
+    fn forwards(&self, id: NodeId, source: NodeId) -> bool {
+        id == source || self.kinds.get(&id) == Some(&NodeType::Drone)
+    }
+
+    /// Returns the hop count of the shortest path from `source` to every node it reaches
+    pub(crate) fn distances(&self, source: NodeId) -> BTreeMap<NodeId, usize> {
+        let mut distances = BTreeMap::from([(source, 0)]);
+        let mut queue = VecDeque::from([source]);
+
+        while let Some(id) = queue.pop_front() {
+            if !self.forwards(id, source) {
+                continue;
+            }
+            let distance = distances[&id];
+            for neighbour in self.neighbours(id) {
+                if !distances.contains_key(neighbour) {
+                    distances.insert(*neighbour, distance + 1);
+                    queue.push_back(*neighbour);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Returns every (client, server) pair connected by a path
+    pub(crate) fn reachable_pairs(&self) -> Vec<(NodeId, NodeId)> {
+        let servers = self.of_kind(NodeType::Server);
+        self.of_kind(NodeType::Client)
+            .into_iter()
+            .flat_map(|client| {
+                let distances = self.distances(client);
+                servers
+                    .iter()
+                    .filter(move |server| distances.contains_key(server))
+                    .map(move |server| (client, *server))
+            })
+            .collect()
+    }
+
+    /// Returns the cheapest path from `source` to every node it reaches, where a path costs the sum of
+    /// `cost` over its intermediate nodes. Nodes with an infinite cost are never crossed.
+    /// Among paths of equal cost the lowest one compared hop by hop is chosen,
+    /// e.g. `[10, 1, 5, 20]` rather than `[10, 2, 4, 20]`
+    pub(crate) fn cheapest_paths<F: Fn(NodeId) -> f64>(
+        &self,
+        source: NodeId,
+        cost: F,
+    ) -> BTreeMap<NodeId, (f64, Vec<NodeId>)> {
+        // Extending a path never makes it cheaper nor lower, so the nodes are settled in the order
+        // of their final (cost, path) and a settled node is never improved
+        let mut best: BTreeMap<NodeId, (f64, Vec<NodeId>)> =
+            BTreeMap::from([(source, (0.0, vec![source]))]);
+        let mut done: BTreeSet<NodeId> = BTreeSet::new();
+
+        while let Some((id, (path_cost, path))) = best
+            .iter()
+            .filter(|(id, _)| !done.contains(*id))
+            .min_by(|a, b| a.1 .0.total_cmp(&b.1 .0).then_with(|| a.1 .1.cmp(&b.1 .1)))
+            .map(|(id, label)| (*id, label.clone()))
+        {
+            done.insert(id);
+            if !self.forwards(id, source) {
+                continue;
+            }
+
+            let step = if id == source { 0.0 } else { cost(id) };
+            let next_cost = path_cost + step;
+            if !next_cost.is_finite() {
+                continue;
+            }
+            for neighbour in self.neighbours(id) {
+                if done.contains(neighbour) {
+                    continue;
+                }
+                let mut next_path = path.clone();
+                next_path.push(*neighbour);
+                let better = best
+                    .get(neighbour)
+                    .is_none_or(|(c, p)| next_cost < *c || (next_cost == *c && next_path < *p));
+                if better {
+                    best.insert(*neighbour, (next_cost, next_path));
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Returns the (client, server) pairs that are disconnected when `id` is removed
+    pub(crate) fn lost_pairs_without_node(&self, id: NodeId) -> Vec<(NodeId, NodeId)> {
+        let after = self.without_node(id).reachable_pairs();
+        self.reachable_pairs()
+            .into_iter()
+            .filter(|pair| pair.0 != id && pair.1 != id && !after.contains(pair))
+            .collect()
+    }
+
+    /// Returns the (client, server) pairs that are disconnected when the edge `a`-`b` is removed
+    pub(crate) fn lost_pairs_without_edge(&self, a: NodeId, b: NodeId) -> Vec<(NodeId, NodeId)> {
+        let after = self.without_edge(a, b).reachable_pairs();
+        self.reachable_pairs()
+            .into_iter()
+            .filter(|pair| !after.contains(pair))
+            .collect()
+    }
+
+    fn without_node(&self, id: NodeId) -> Graph {
+        let mut graph = self.clone();
+        graph.kinds.remove(&id);
+        graph.adjacency.remove(&id);
+        for neighbours in graph.adjacency.values_mut() {
+            neighbours.retain(|n| *n != id);
+        }
+        graph
+    }
+
+    fn without_edge(&self, a: NodeId, b: NodeId) -> Graph {
+        let mut graph = self.clone();
+        if let Some(neighbours) = graph.adjacency.get_mut(&a) {
+            neighbours.retain(|n| *n != b);
+        }
+        if let Some(neighbours) = graph.adjacency.get_mut(&b) {
+            neighbours.retain(|n| *n != a);
+        }
+        graph
+    }
+
+    /// Maximum number of paths from `source` to `target` not sharing any edge
+    pub(crate) fn edge_disjoint_paths(&self, source: NodeId, target: NodeId) -> usize {
+        // Each undirected edge has capacity one in both directions
+        let mut residual: HashMap<(NodeId, NodeId), u32> = self
+            .adjacency
+            .iter()
+            .flat_map(|(a, neighbours)| neighbours.iter().map(move |b| ((*a, *b), 1)))
+            .collect();
+        let mut paths = 0;
+
+        loop {
+            let mut previous: HashMap<NodeId, NodeId> = HashMap::new();
+            let mut queue = VecDeque::from([source]);
+            while let Some(id) = queue.pop_front() {
+                if id == target || !self.forwards(id, source) {
+                    continue;
+                }
+                for neighbour in self.neighbours(id) {
+                    if *neighbour != source
+                        && !previous.contains_key(neighbour)
+                        && residual.get(&(id, *neighbour)).is_some_and(|c| *c > 0)
+                    {
+                        previous.insert(*neighbour, id);
+                        queue.push_back(*neighbour);
+                    }
+                }
+            }
+
+            if !previous.contains_key(&target) {
+                return paths;
+            }
+
+            let mut id = target;
+            while id != source {
+                let from = previous[&id];
+                *residual.entry((from, id)).or_default() -= 1;
+                *residual.entry((id, from)).or_default() += 1;
+                id = from;
+            }
+            paths += 1;
+        }
+    }
+
+    /// Betweenness centrality of every node, counting each unordered pair once (Brandes' algorithm)
+    pub(crate) fn betweenness(&self) -> BTreeMap<NodeId, f64> {
+        let mut centrality: BTreeMap<NodeId, f64> = self.nodes().map(|id| (id, 0.0)).collect();
+
+        for source in self.nodes() {
+            let mut order = Vec::new();
+            let mut predecessors: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+            let mut paths: HashMap<NodeId, f64> = HashMap::from([(source, 1.0)]);
+            let mut distances: HashMap<NodeId, usize> = HashMap::from([(source, 0)]);
+            let mut queue = VecDeque::from([source]);
+
+            while let Some(id) = queue.pop_front() {
+                order.push(id);
+                if !self.forwards(id, source) {
+                    continue;
+                }
+                let distance = distances[&id];
+                for neighbour in self.neighbours(id) {
+                    if !distances.contains_key(neighbour) {
+                        distances.insert(*neighbour, distance + 1);
+                        queue.push_back(*neighbour);
+                    }
+                    if distances[neighbour] == distance + 1 {
+                        *paths.entry(*neighbour).or_default() += paths[&id];
+                        predecessors.entry(*neighbour).or_default().push(id);
+                    }
+                }
+            }
+
+            let mut dependency: HashMap<NodeId, f64> = HashMap::new();
+            for id in order.into_iter().rev() {
+                let delta = dependency.get(&id).copied().unwrap_or_default();
+                for predecessor in predecessors.get(&id).map_or(&[][..], Vec::as_slice) {
+                    *dependency.entry(*predecessor).or_default() +=
+                        paths[predecessor] / paths[&id] * (1.0 + delta);
+                }
+                if id != source {
+                    *centrality.entry(id).or_default() += delta;
+                }
+            }
+        }
+
+        // Every pair was counted from both of its ends
+        for value in centrality.values_mut() {
+            *value /= 2.0;
+        }
+        centrality
+    }
+}
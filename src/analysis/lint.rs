@@ -0,0 +1,69 @@
+use std::fmt::{self, Display};
+
+use wg_internal::network::NodeId;
+
+use crate::{parsed_nodes::NodeType, utils::parser::Parser};
+
+use super::graph::Graph;
+
+/// Single point of failure of a topology that passes `check_topology`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TopologyWarning {
+    /// The drone is the only path between the clients and servers of `affected`
+    ArticulationDrone {
+        drone: NodeId,
+        affected: Vec<(NodeId, NodeId)>,
+    },
+    /// The link is the only path between the clients and servers of `affected`
+    BridgeLink {
+        a: NodeId,
+        b: NodeId,
+        affected: Vec<(NodeId, NodeId)>,
+    },
+}
+
+impl Display for TopologyWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TopologyWarning::ArticulationDrone { drone, affected } => write!(
+                f,
+                "Drone {drone} is a single point of failure for the (client, server) pairs {affected:?}"
+            ),
+            TopologyWarning::BridgeLink { a, b, affected } => write!(
+                f,
+                "Link {a}-{b} is a single point of failure for the (client, server) pairs {affected:?}"
+            ),
+        }
+    }
+}
+
+/// What to do with the warnings of `lint_topology` when the simulation starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LintPolicy {
+    /// Print the warnings and launch the network anyway
+    #[default]
+    Warn,
+    /// Treat the warnings as errors and refuse to launch the network
+    Deny,
+}
+
+/// Find the drones and links whose failure would disconnect a client from a server
+#[must_use]
+pub fn lint_topology(parser: &Parser) -> Vec<TopologyWarning> {
+    let graph = Graph::new(parser);
+
+    let drones = graph
+        .of_kind(NodeType::Drone)
+        .into_iter()
+        .filter_map(|drone| {
+            let affected = graph.lost_pairs_without_node(drone);
+            (!affected.is_empty()).then_some(TopologyWarning::ArticulationDrone { drone, affected })
+        });
+
+    let links = graph.edges().into_iter().filter_map(|(a, b)| {
+        let affected = graph.lost_pairs_without_edge(a, b);
+        (!affected.is_empty()).then_some(TopologyWarning::BridgeLink { a, b, affected })
+    });
+
+    drones.chain(links).collect()
+}
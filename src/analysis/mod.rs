@@ -0,0 +1,6 @@
+pub(crate) mod graph;
+pub mod lint;
+pub mod reliability;
+pub mod routes;
+pub mod topology;
+pub mod traffic;
@@ -0,0 +1,99 @@
+use std::fmt::{self, Display};
+
+use wg_internal::network::NodeId;
+
+use crate::{parsed_nodes::NodeType, utils::parser::Parser};
+
+use super::graph::Graph;
+
+/// Delivery estimate of the fragments sent between a client and a server
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairReliability {
+    pub client: NodeId,
+    pub server: NodeId,
+    /// Most reliable path, from the client to the server. Empty if every path crosses a drone with PDR 1
+    pub best_path: Vec<NodeId>,
+    /// Probability that a fragment sent along `best_path` is not dropped by any drone
+    pub delivery_probability: f64,
+    /// Transmissions needed on average to deliver a fragment, `None` if it is never delivered
+    pub expected_transmissions: Option<f64>,
+}
+
+/// Reliability of every (client, server) pair of a topology
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReliabilityReport {
+    pub pairs: Vec<PairReliability>,
+}
+
+impl ReliabilityReport {
+    #[must_use]
+    pub fn pair(&self, client: NodeId, server: NodeId) -> Option<&PairReliability> {
+        self.pairs
+            .iter()
+            .find(|pair| pair.client == client && pair.server == server)
+    }
+}
+
+/// Estimate the reliability of the best path between every client and server from the drone PDRs.
+/// Every drone of the path drops a fragment with probability `pdr`, and a dropped fragment is sent again
+#[must_use]
+pub fn estimate_reliability(parser: &Parser) -> ReliabilityReport {
+    let graph = Graph::new(parser);
+    let servers = graph.of_kind(NodeType::Server);
+    let mut pairs = Vec::new();
+
+    for client in graph.of_kind(NodeType::Client) {
+        // Maximizing the product of the (1 - pdr) is minimizing the sum of their negated logarithms
+        let paths = graph.cheapest_paths(client, |id| -(1.0 - graph.pdr(id)).ln());
+
+        for server in &servers {
+            let best_path = paths
+                .get(server)
+                .map(|(_, path)| path.clone())
+                .unwrap_or_default();
+            let delivery_probability = if best_path.is_empty() {
+                0.0
+            } else {
+                best_path[1..best_path.len() - 1]
+                    .iter()
+                    .map(|id| 1.0 - graph.pdr(*id))
+                    .product()
+            };
+
+            pairs.push(PairReliability {
+                client,
+                server: *server,
+                best_path,
+                delivery_probability,
+                expected_transmissions: (delivery_probability > 0.0)
+                    .then(|| 1.0 / delivery_probability),
+            });
+        }
+    }
+
+    ReliabilityReport { pairs }
+}
+
+impl Display for ReliabilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Reliability:")?;
+        for pair in &self.pairs {
+            write!(
+                f,
+                "  {} -> {}: delivery {:.1}%",
+                pair.client,
+                pair.server,
+                pair.delivery_probability * 100.0
+            )?;
+            match pair.expected_transmissions {
+                Some(transmissions) => writeln!(
+                    f,
+                    ", {transmissions:.2} transmissions per fragment via {:?}",
+                    pair.best_path
+                )?,
+                None => writeln!(f, ", never delivered")?,
+            }
+        }
+        Ok(())
+    }
+}
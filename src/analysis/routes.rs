@@ -0,0 +1,65 @@
+use std::collections::BTreeMap;
+
+use wg_internal::network::{NodeId, SourceRoutingHeader};
+
+use crate::{parsed_nodes::NodeType, utils::parser::Parser};
+
+use super::graph::Graph;
+
+/// What a reference route minimizes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RouteMetric {
+    /// Number of hops
+    #[default]
+    HopCount,
+    /// Probability of a fragment being dropped, from the PDRs of the drones
+    Pdr,
+}
+
+/// Reference route from every client to every server it reaches.
+/// The route from a server to a client is the reversed one
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RouteTable {
+    /// Hops of each (client, server) route, from the client to the server
+    pub routes: BTreeMap<(NodeId, NodeId), Vec<NodeId>>,
+}
+
+impl RouteTable {
+    #[must_use]
+    pub fn route(&self, client: NodeId, server: NodeId) -> Option<&Vec<NodeId>> {
+        self.routes.get(&(client, server))
+    }
+
+    /// Returns the header of a packet sent by `client` to `server`, with `hop_index` pointing to the first drone
+    #[must_use]
+    pub fn header(&self, client: NodeId, server: NodeId) -> Option<SourceRoutingHeader> {
+        self.route(client, server).map(|hops| SourceRoutingHeader {
+            hop_index: 1,
+            hops: hops.clone(),
+        })
+    }
+}
+
+/// Compute the best route from every client to every server, only drones are intermediate hops.
+/// Among equally good routes the lowest one compared hop by hop is chosen
+#[must_use]
+pub fn reference_routes(parser: &Parser, metric: RouteMetric) -> RouteTable {
+    let graph = Graph::new(parser);
+    let servers = graph.of_kind(NodeType::Server);
+    let mut routes = BTreeMap::new();
+
+    for client in graph.of_kind(NodeType::Client) {
+        let paths = match metric {
+            RouteMetric::HopCount => graph.cheapest_paths(client, |_| 1.0),
+            RouteMetric::Pdr => graph.cheapest_paths(client, |id| -(1.0 - graph.pdr(id)).ln()),
+        };
+
+        for server in &servers {
+            if let Some((_, hops)) = paths.get(server) {
+                routes.insert((client, *server), hops.clone());
+            }
+        }
+    }
+
+    RouteTable { routes }
+}
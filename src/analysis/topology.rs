@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+use wg_internal::network::NodeId;
+
+use crate::{parsed_nodes::NodeType, utils::parser::Parser};
+
+use super::graph::Graph;
+
+/// Structure of a topology, paths only use drones as intermediate hops
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopologyReport {
+    pub degrees: BTreeMap<NodeId, usize>,
+    /// Longest shortest path in hops between two connected nodes, `None` without any pair
+    pub diameter: Option<usize>,
+    /// Average hops of the shortest path between a client and a server it reaches
+    pub average_client_server_path: Option<f64>,
+    /// Number of paths not sharing any edge for every (client, server) pair
+    pub edge_disjoint_paths: BTreeMap<(NodeId, NodeId), usize>,
+    /// Drones whose removal disconnects a client from a server it reached
+    pub articulation_points: Vec<NodeId>,
+    /// Betweenness centrality of every drone
+    pub betweenness: BTreeMap<NodeId, f64>,
+}
+
+/// Compute the metrics of a topology
+#[must_use]
+pub fn analyze_topology(parser: &Parser) -> TopologyReport {
+    let graph = Graph::new(parser);
+    let clients = graph.of_kind(NodeType::Client);
+    let servers = graph.of_kind(NodeType::Server);
+
+    let degrees = graph
+        .nodes()
+        .map(|id| (id, graph.neighbours(id).len()))
+        .collect();
+
+    let diameter = graph
+        .nodes()
+        .flat_map(|id| graph.distances(id).into_values())
+        .max()
+        .filter(|diameter| *diameter > 0);
+
+    let client_server_paths: Vec<usize> = clients
+        .iter()
+        .flat_map(|client| {
+            let distances = graph.distances(*client);
+            servers
+                .iter()
+                .filter_map(move |server| distances.get(server).copied())
+        })
+        .collect();
+    #[allow(clippy::cast_precision_loss)]
+    let average_client_server_path = (!client_server_paths.is_empty()).then(|| {
+        client_server_paths.iter().sum::<usize>() as f64 / client_server_paths.len() as f64
+    });
+
+    let edge_disjoint_paths = clients
+        .iter()
+        .flat_map(|client| servers.iter().map(move |server| (*client, *server)))
+        .map(|(client, server)| ((client, server), graph.edge_disjoint_paths(client, server)))
+        .collect();
+
+    let drones = graph.of_kind(NodeType::Drone);
+    let betweenness = graph
+        .betweenness()
+        .into_iter()
+        .filter(|(id, _)| drones.contains(id))
+        .collect();
+
+    TopologyReport {
+        degrees,
+        diameter,
+        average_client_server_path,
+        edge_disjoint_paths,
+        articulation_points: graph
+            .of_kind(NodeType::Drone)
+            .into_iter()
+            .filter(|drone| !graph.lost_pairs_without_node(*drone).is_empty())
+            .collect(),
+        betweenness,
+    }
+}
+
+impl Display for TopologyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.diameter {
+            Some(diameter) => writeln!(f, "Diameter: {diameter} hops")?,
+            None => writeln!(f, "Diameter: -")?,
+        }
+        match self.average_client_server_path {
+            Some(average) => writeln!(f, "Average client-server path: {average:.2} hops")?,
+            None => writeln!(f, "Average client-server path: -")?,
+        }
+        writeln!(f, "Articulation drones: {:?}", self.articulation_points)?;
+
+        writeln!(f, "Degrees:")?;
+        for (id, degree) in &self.degrees {
+            writeln!(f, "  {id}: {degree}")?;
+        }
+        writeln!(f, "Edge-disjoint paths:")?;
+        for ((client, server), paths) in &self.edge_disjoint_paths {
+            writeln!(f, "  {client} -> {server}: {paths}")?;
+        }
+        writeln!(f, "Drone betweenness:")?;
+        for (id, value) in &self.betweenness {
+            writeln!(f, "  {id}: {value:.2}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,276 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display};
+
+use wg_internal::network::NodeId;
+
+use crate::capture::{CapturedNackType, CapturedPacket, CapturedPacketType};
+
+/// Where a fragment was lost
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DropLocation {
+    /// The drone dropped the fragment and sent back a `Dropped` nack
+    Drone(NodeId),
+    /// The link from the first node to the second one lost the fragment
+    Link(NodeId, NodeId),
+}
+
+/// What happened to a single fragment of a session
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FragmentFlow {
+    /// Times the source sent the fragment
+    pub transmissions: u32,
+    pub delivered: bool,
+    /// Hops of the route that delivered the fragment
+    pub hop_count: Option<usize>,
+    pub drops: Vec<DropLocation>,
+}
+
+impl FragmentFlow {
+    #[must_use]
+    pub fn retransmissions(&self) -> u32 {
+        self.transmissions.saturating_sub(1)
+    }
+}
+
+/// Flow of the fragments of a session from its source to its destination
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionFlow {
+    pub session_id: u64,
+    pub source: NodeId,
+    pub destination: NodeId,
+    pub total_n_fragments: u64,
+    pub fragments: BTreeMap<u64, FragmentFlow>,
+    /// Nacks of the session as `(fragment_index, nack_type)`
+    pub nacks: Vec<(u64, CapturedNackType)>,
+    pub acks: u64,
+}
+
+impl SessionFlow {
+    fn new(session_id: u64, source: NodeId, destination: NodeId, total_n_fragments: u64) -> Self {
+        SessionFlow {
+            session_id,
+            source,
+            destination,
+            total_n_fragments,
+            fragments: BTreeMap::new(),
+            nacks: Vec::new(),
+            acks: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn delivered_fragments(&self) -> u64 {
+        self.fragments.values().filter(|f| f.delivered).count() as u64
+    }
+
+    /// Ratio between the delivered fragments and the fragments of the message
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn delivery_ratio(&self) -> f64 {
+        if self.total_n_fragments == 0 {
+            return 0.0;
+        }
+        self.delivered_fragments() as f64 / self.total_n_fragments as f64
+    }
+
+    #[must_use]
+    pub fn retransmissions(&self) -> u32 {
+        self.fragments
+            .values()
+            .map(FragmentFlow::retransmissions)
+            .sum()
+    }
+}
+
+/// Flows reconstructed from a capture
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrafficReport {
+    /// Sessions by `(source, session_id)`, as every node numbers its own sessions
+    pub sessions: BTreeMap<(NodeId, u64), SessionFlow>,
+    /// Fragments dropped by each drone
+    pub drone_drops: BTreeMap<NodeId, u64>,
+    /// Fragments lost by each link
+    pub link_losses: BTreeMap<(NodeId, NodeId), u64>,
+    /// Floods as `(initiator_id, flood_id)`
+    pub floods: BTreeSet<(NodeId, u64)>,
+    pub flood_requests: u64,
+    pub flood_responses: u64,
+}
+
+impl TrafficReport {
+    #[must_use]
+    pub fn session(&self, source: NodeId, session_id: u64) -> Option<&SessionFlow> {
+        self.sessions.get(&(source, session_id))
+    }
+
+    /// Get the sessions of every source with the given id
+    pub fn sessions_with_id(&self, session_id: u64) -> impl Iterator<Item = &SessionFlow> {
+        self.sessions
+            .values()
+            .filter(move |session| session.session_id == session_id)
+    }
+}
+
+/// Key of the session an ack or a nack answers, whose source is the destination of their route
+fn session_key(packet: &CapturedPacket) -> (NodeId, u64) {
+    let source = packet.hops.last().copied().unwrap_or(packet.to);
+    (source, packet.session_id)
+}
+
+/// Reconstruct the flow of every session from the captured packets.
+/// A fragment is transmitted when its source sends it and delivered when it crosses the last link of its route.
+/// Acks and nacks are matched to the session of the destination of their route, when they leave the node that created them.
+#[must_use]
+pub fn analyze_traffic(packets: &[CapturedPacket]) -> TrafficReport {
+    let mut report = TrafficReport::default();
+
+    for packet in packets {
+        match &packet.pack_type {
+            CapturedPacketType::MsgFragment {
+                fragment_index,
+                total_n_fragments,
+                ..
+            } => {
+                let (Some(source), Some(destination)) = (packet.hops.first(), packet.hops.last())
+                else {
+                    continue;
+                };
+                let session = report
+                    .sessions
+                    .entry((*source, packet.session_id))
+                    .or_insert_with(|| {
+                        SessionFlow::new(
+                            packet.session_id,
+                            *source,
+                            *destination,
+                            *total_n_fragments,
+                        )
+                    });
+                let fragment = session.fragments.entry(*fragment_index).or_default();
+
+                if packet.from == *source {
+                    fragment.transmissions += 1;
+                }
+                if packet.lost_on_link {
+                    fragment
+                        .drops
+                        .push(DropLocation::Link(packet.from, packet.to));
+                    *report
+                        .link_losses
+                        .entry((packet.from, packet.to))
+                        .or_default() += 1;
+                } else if packet.to == *destination && !fragment.delivered {
+                    fragment.delivered = true;
+                    fragment.hop_count = Some(packet.hops.len() - 1);
+                }
+            }
+            CapturedPacketType::Ack { .. } => {
+                if packet.hops.first() != Some(&packet.from) {
+                    continue;
+                }
+                if let Some(session) = report.sessions.get_mut(&session_key(packet)) {
+                    session.acks += 1;
+                }
+            }
+            CapturedPacketType::Nack {
+                fragment_index,
+                nack_type,
+            } => {
+                // The nack is only counted when it leaves the node that created it
+                if packet.hops.first() != Some(&packet.from) {
+                    continue;
+                }
+                if *nack_type == CapturedNackType::Dropped {
+                    *report.drone_drops.entry(packet.from).or_default() += 1;
+                }
+                if let Some(session) = report.sessions.get_mut(&session_key(packet)) {
+                    session.nacks.push((*fragment_index, *nack_type));
+
+                    if *nack_type == CapturedNackType::Dropped {
+                        session
+                            .fragments
+                            .entry(*fragment_index)
+                            .or_default()
+                            .drops
+                            .push(DropLocation::Drone(packet.from));
+                    }
+                }
+            }
+            CapturedPacketType::FloodRequest {
+                flood_id,
+                initiator_id,
+                ..
+            } => {
+                report.floods.insert((*initiator_id, *flood_id));
+                report.flood_requests += 1;
+            }
+            CapturedPacketType::FloodResponse { .. } => {
+                report.flood_responses += 1;
+            }
+        }
+    }
+
+    report
+}
+
+impl Display for SessionFlow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Session {} {} -> {}: {}/{} fragments delivered ({:.1}%), {} retransmissions, {} acks, {} nacks",
+            self.session_id,
+            self.source,
+            self.destination,
+            self.delivered_fragments(),
+            self.total_n_fragments,
+            self.delivery_ratio() * 100.0,
+            self.retransmissions(),
+            self.acks,
+            self.nacks.len()
+        )?;
+
+        for (index, fragment) in &self.fragments {
+            if fragment.drops.is_empty() && fragment.transmissions <= 1 {
+                continue;
+            }
+            write!(
+                f,
+                "  fragment {index}: sent {} times, delivered: {}",
+                fragment.transmissions, fragment.delivered
+            )?;
+            if let Some(hop_count) = fragment.hop_count {
+                write!(f, " in {hop_count} hops")?;
+            }
+            if !fragment.drops.is_empty() {
+                write!(f, ", dropped at {:?}", fragment.drops)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for TrafficReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for session in self.sessions.values() {
+            write!(f, "{session}")?;
+        }
+
+        writeln!(
+            f,
+            "Floods: {} ({} requests, {} responses)",
+            self.floods.len(),
+            self.flood_requests,
+            self.flood_responses
+        )?;
+        for (drone, drops) in &self.drone_drops {
+            writeln!(f, "Drone {drone} dropped {drops} fragments")?;
+        }
+        for ((from, to), losses) in &self.link_losses {
+            writeln!(f, "Link {from} -> {to} lost {losses} fragments")?;
+        }
+
+        Ok(())
+    }
+}
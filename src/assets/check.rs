@@ -0,0 +1,127 @@
+use std::fs;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+
+use crate::errors::AssetError;
+use crate::manifest::NodeManifest;
+
+use super::metadata::{
+    SongList, VideoList, CLIENT_SONGS_FILE, CLIENT_VIDEOS_FILE, PLAYLIST_FILE, SERVER_SONGS_FILE,
+    SERVER_VIDEOS_FILE, SONGS_DIR, VIDEOS_DIR,
+};
+use super::{content_name, AssetKind};
+
+fn display(path: &Path) -> String {
+    path.display().to_string()
+}
+
+/// Parse a metadata file, `None` if it is missing or malformed
+fn read_metadata<T: DeserializeOwned>(path: &Path, errors: &mut Vec<AssetError>) -> Option<T> {
+    let Ok(data) = fs::read_to_string(path) else {
+        errors.push(AssetError::MissingFile(display(path)));
+        return None;
+    };
+    serde_json::from_str(&data)
+        .map_err(|_| errors.push(AssetError::InvalidMetadata(display(path))))
+        .ok()
+}
+
+/// Check that the playlist of a song exists and that every segment it references exists
+fn check_playlist(song_dir: &Path, title: &str, errors: &mut Vec<AssetError>) {
+    let playlist = song_dir.join(PLAYLIST_FILE);
+    if !song_dir.is_dir() {
+        errors.push(AssetError::MissingContent {
+            path: display(song_dir),
+            title: title.to_string(),
+        });
+        return;
+    }
+    let Ok(data) = fs::read_to_string(&playlist) else {
+        errors.push(AssetError::MissingFile(display(&playlist)));
+        return;
+    };
+
+    let segments: Vec<&str> = data
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+    if segments.is_empty() {
+        errors.push(AssetError::EmptyPlaylist(display(&playlist)));
+    }
+    for segment in segments {
+        if !song_dir.join(segment).is_file() {
+            errors.push(AssetError::MissingSegment {
+                playlist: display(&playlist),
+                segment: segment.to_string(),
+            });
+        }
+    }
+}
+
+fn check_songs(dir: &Path, metadata_file: &str, errors: &mut Vec<AssetError>) {
+    let Some(list) = read_metadata::<SongList>(&dir.join(metadata_file), errors) else {
+        return;
+    };
+    for song in &list.songs {
+        let song_dir = dir.join(SONGS_DIR).join(content_name(&song.title));
+        check_playlist(&song_dir, &song.title, errors);
+    }
+}
+
+fn check_videos(dir: &Path, metadata_file: &str, errors: &mut Vec<AssetError>) {
+    let Some(list) = read_metadata::<VideoList>(&dir.join(metadata_file), errors) else {
+        return;
+    };
+    for video in &list.videos {
+        let file = dir
+            .join(VIDEOS_DIR)
+            .join(format!("{}.mp4", content_name(&video.title)));
+        if !file.is_file() {
+            errors.push(AssetError::MissingContent {
+                path: display(&file),
+                title: video.title.clone(),
+            });
+        }
+    }
+}
+
+/// Check the initialization folder of a node, returning every problem found
+#[must_use]
+pub fn check_asset_dir(path: &str, kind: AssetKind) -> Vec<AssetError> {
+    let dir = Path::new(path);
+    if !dir.is_dir() {
+        return vec![AssetError::MissingDirectory(path.to_string())];
+    }
+
+    let mut errors = Vec::new();
+    match kind {
+        AssetKind::ClientAudio => check_songs(dir, CLIENT_SONGS_FILE, &mut errors),
+        AssetKind::ClientVideo => check_videos(dir, CLIENT_VIDEOS_FILE, &mut errors),
+        AssetKind::Server => {
+            check_songs(dir, SERVER_SONGS_FILE, &mut errors);
+            check_videos(dir, SERVER_VIDEOS_FILE, &mut errors);
+        }
+    }
+    errors
+}
+
+/// Check the folder of every client and server of the manifest, each folder is checked once
+#[must_use]
+pub fn check_manifest_assets(manifest: &NodeManifest) -> Vec<AssetError> {
+    let mut checked = Vec::new();
+    let mut errors = Vec::new();
+
+    for entry in &manifest.nodes {
+        let (Some(kind), Some(path)) = (AssetKind::of(entry), &entry.asset_path) else {
+            continue;
+        };
+        if checked.contains(&(kind, path)) {
+            continue;
+        }
+        checked.push((kind, path));
+        errors.extend(check_asset_dir(path, kind));
+    }
+    errors
+}
@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::fs;
+use std::hash::Hash;
+use std::path::Path;
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::ser::PrettyFormatter;
+use serde_json::{Serializer, Value};
+use wg_internal::network::NodeId;
+
+use crate::errors::AssetError;
+use crate::manifest::NodeManifest;
+
+use super::metadata::{
+    SongList, VideoList, CLIENT_SONGS_FILE, CLIENT_VIDEOS_FILE, SERVER_SONGS_FILE,
+    SERVER_VIDEOS_FILE,
+};
+use super::{AssetKind, AssetLayout};
+
+/// What to do when two nodes would publish the same title or content id
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateTitlePolicy {
+    /// Print a warning and launch the network anyway
+    #[default]
+    Warn,
+    /// Refuse to launch the network
+    Fail,
+}
+
+/// Metadata files of a folder of the given kind
+fn metadata_files(kind: AssetKind) -> (Option<&'static str>, Option<&'static str>) {
+    match kind {
+        AssetKind::ClientAudio => (Some(CLIENT_SONGS_FILE), None),
+        AssetKind::ClientVideo => (None, Some(CLIENT_VIDEOS_FILE)),
+        AssetKind::Server => (Some(SERVER_SONGS_FILE), Some(SERVER_VIDEOS_FILE)),
+    }
+}
+
+/// Parse a metadata file, missing and malformed files are reported by the asset checker
+fn read_list<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let data = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+/// Serialize `json` with the indentation of `original` and keep its final newline,
+/// so that a file changes only where its ids do
+fn write_like(path: &Path, json: &Value, original: &str) -> Result<(), AssetError> {
+    let write_error = || AssetError::WriteError(path.display().to_string());
+    let indent: String = original
+        .lines()
+        .nth(1)
+        .map(|line| {
+            line.chars()
+                .take_while(|c| *c == ' ' || *c == '\t')
+                .collect()
+        })
+        .filter(|indent: &String| !indent.is_empty())
+        .unwrap_or_else(|| "  ".to_string());
+
+    let mut data = Vec::new();
+    let mut serializer =
+        Serializer::with_formatter(&mut data, PrettyFormatter::with_indent(indent.as_bytes()));
+    json.serialize(&mut serializer).map_err(|_| write_error())?;
+    if original.ends_with('\n') {
+        data.push(b'\n');
+    }
+    fs::write(path, data).map_err(|_| write_error())
+}
+
+/// Returns the next id, starting from 1
+fn next_id(counter: &mut u16) -> Result<u16, AssetError> {
+    *counter = counter
+        .checked_add(1)
+        .ok_or(AssetError::ContentIdOverflow)?;
+    Ok(*counter)
+}
+
+/// Give the next ids to the contents listed under `key` in the metadata file at `path`, a `T`.
+/// Only the ids are rewritten, the other fields and the formatting of the file are kept.
+/// Missing and malformed files are skipped, they are reported by the asset checker
+fn assign_file_ids<T: DeserializeOwned>(
+    path: &Path,
+    key: &str,
+    counter: &mut u16,
+) -> Result<(), AssetError> {
+    let Ok(original) = fs::read_to_string(path) else {
+        return Ok(());
+    };
+    let Ok(mut json) = serde_json::from_str::<Value>(&original) else {
+        return Ok(());
+    };
+    if serde_json::from_value::<T>(json.clone()).is_err() {
+        return Ok(());
+    }
+    let Some(contents) = json.get_mut(key).and_then(Value::as_array_mut) else {
+        return Ok(());
+    };
+
+    let mut changed = false;
+    for content in contents.iter_mut().filter_map(Value::as_object_mut) {
+        let id = next_id(counter)?;
+        changed |= content.get("id").and_then(Value::as_u64) != Some(u64::from(id));
+        content.insert("id".to_string(), Value::from(id));
+    }
+
+    if changed {
+        write_like(path, &json, &original)?;
+    }
+    Ok(())
+}
+
+/// Give every song and video of the folders of `layout` a unique id, rewriting their metadata files.
+/// Folders are visited in a fixed order (audio clients, video clients, servers), so the ids only depend
+/// on the layout and files are rewritten only when their ids change.
+/// It is run by `generate_assets` and the `assign-content-ids` command, never when the network is launched
+/// # Errors
+/// Returns an error if there are more than `u16::MAX` contents or a metadata file cannot be written
+pub fn assign_content_ids(layout: &AssetLayout) -> Result<(), AssetError> {
+    let mut counter = 0;
+    let folders = [
+        (AssetKind::ClientAudio, layout.client_audio),
+        (AssetKind::ClientVideo, layout.client_video),
+        (AssetKind::Server, layout.servers),
+    ];
+
+    for (kind, count) in folders {
+        for i in 0..count {
+            let dir = layout.path(kind, i);
+            let (songs_file, videos_file) = metadata_files(kind);
+
+            if let Some(file) = songs_file {
+                assign_file_ids::<SongList>(&Path::new(&dir).join(file), "songs", &mut counter)?;
+            }
+            if let Some(file) = videos_file {
+                assign_file_ids::<VideoList>(&Path::new(&dir).join(file), "videos", &mut counter)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the ids and titles of the songs and videos of a folder
+fn contents(dir: &str, kind: AssetKind) -> Vec<(u16, String)> {
+    let (songs_file, videos_file) = metadata_files(kind);
+    let songs = songs_file
+        .and_then(|file| read_list::<SongList>(&Path::new(dir).join(file)))
+        .map(|list| list.songs.into_iter().map(|song| (song.id, song.title)));
+    let videos = videos_file
+        .and_then(|file| read_list::<VideoList>(&Path::new(dir).join(file)))
+        .map(|list| list.videos.into_iter().map(|video| (video.id, video.title)));
+
+    songs
+        .into_iter()
+        .flatten()
+        .chain(videos.into_iter().flatten())
+        .collect()
+}
+
+/// Find the keys of the contents published by more than one client or server of the manifest
+fn find_duplicates<K: Eq + Hash>(
+    manifest: &NodeManifest,
+    key: impl Fn((u16, String)) -> K,
+    error: impl Fn(K, NodeId, NodeId) -> AssetError,
+) -> Vec<AssetError> {
+    let mut publishers: HashMap<K, NodeId> = HashMap::new();
+    let mut duplicates = Vec::new();
+
+    for entry in &manifest.nodes {
+        let (Some(kind), Some(path)) = (AssetKind::of(entry), &entry.asset_path) else {
+            continue;
+        };
+        for content in contents(path, kind) {
+            let key = key(content);
+            match publishers.get(&key) {
+                Some(first) => duplicates.push(error(key, *first, entry.id)),
+                None => {
+                    publishers.insert(key, entry.id);
+                }
+            }
+        }
+    }
+
+    duplicates
+}
+
+/// Find the titles published by more than one client or server of the manifest,
+/// e.g. because two nodes share the same folder
+#[must_use]
+pub fn find_duplicate_titles(manifest: &NodeManifest) -> Vec<AssetError> {
+    find_duplicates(
+        manifest,
+        |(_, title)| title,
+        |title, first, second| AssetError::DuplicateTitle {
+            title,
+            first,
+            second,
+        },
+    )
+}
+
+/// Find the content ids used by more than one client or server of the manifest, e.g. because
+/// two nodes share the same folder or the ids were not assigned with `assign_content_ids`
+#[must_use]
+pub fn find_duplicate_ids(manifest: &NodeManifest) -> Vec<AssetError> {
+    find_duplicates(
+        manifest,
+        |(id, _)| id,
+        |id, first, second| AssetError::DuplicateContentId { id, first, second },
+    )
+}
@@ -0,0 +1,174 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::errors::AssetError;
+
+use super::content_ids::assign_content_ids;
+use super::metadata::{
+    SongList, SongMetadata, VideoList, VideoMetadata, CLIENT_SONGS_FILE, CLIENT_VIDEOS_FILE,
+    PLAYLIST_FILE, SERVER_SONGS_FILE, SERVER_VIDEOS_FILE, SONGS_DIR, VIDEOS_DIR,
+};
+use super::{content_name, AssetKind, AssetLayout};
+
+/// Duration declared for every synthetic segment, in seconds
+const SEGMENT_DURATION: u32 = 5;
+
+/// Number and size of the synthetic assets to generate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetPackConfig {
+    pub client_audio: usize,
+    pub client_video: usize,
+    pub servers: usize,
+    /// Songs of every audio client and server
+    pub songs_per_dir: usize,
+    /// Videos of every video client and server
+    pub videos_per_dir: usize,
+    pub segments_per_song: usize,
+    /// Size of every segment in bytes
+    pub segment_size: usize,
+    /// Size of every mp4 file in bytes
+    pub video_size: usize,
+}
+
+impl Default for AssetPackConfig {
+    fn default() -> Self {
+        AssetPackConfig {
+            client_audio: 3,
+            client_video: 1,
+            servers: 2,
+            songs_per_dir: 2,
+            videos_per_dir: 1,
+            segments_per_song: 3,
+            segment_size: 16 * 1024,
+            video_size: 64 * 1024,
+        }
+    }
+}
+
+fn write(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), AssetError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|_| AssetError::WriteError(parent.display().to_string()))?;
+    }
+    fs::write(path, contents).map_err(|_| AssetError::WriteError(path.display().to_string()))
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), AssetError> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|_| AssetError::WriteError(path.display().to_string()))?;
+    write(path, json)
+}
+
+/// Deterministic payload of `size` bytes, different for every `salt`
+fn blob(size: usize, salt: usize) -> Vec<u8> {
+    (0..size)
+        .map(|i| u8::try_from((i + salt) % 256).unwrap_or_default())
+        .collect()
+}
+
+fn write_song(dir: &Path, title: &str, config: &AssetPackConfig) -> Result<(), AssetError> {
+    let song_dir = dir.join(SONGS_DIR).join(content_name(title));
+
+    let mut playlist = format!(
+        "#EXTM3U\n#EXT-X-VERSION:3\n#EXT-X-TARGETDURATION:{SEGMENT_DURATION}\n#EXT-X-MEDIA-SEQUENCE:0\n#EXT-X-PLAYLIST-TYPE:VOD\n"
+    );
+    for i in 0..config.segments_per_song {
+        let _ = write!(playlist, "#EXTINF:{SEGMENT_DURATION}.0,\nsegment{i}.ts\n");
+        write(
+            &song_dir.join(format!("segment{i}.ts")),
+            blob(config.segment_size, i),
+        )?;
+    }
+    playlist.push_str("#EXT-X-ENDLIST\n");
+
+    write(&song_dir.join(PLAYLIST_FILE), playlist)
+}
+
+fn write_songs(
+    dir: &Path,
+    metadata_file: &str,
+    prefix: &str,
+    config: &AssetPackConfig,
+) -> Result<(), AssetError> {
+    let duration = u32::try_from(config.segments_per_song).unwrap_or(u32::MAX) * SEGMENT_DURATION;
+    let mut list = SongList::default();
+
+    for i in 1..=config.songs_per_dir {
+        let title = format!("{prefix} Song {i}");
+        write_song(dir, &title, config)?;
+        list.songs.push(SongMetadata {
+            id: 0,
+            title,
+            artist: "Synthetic".to_string(),
+            album: "Generated".to_string(),
+            duration,
+            image_url: String::new(),
+            is_local: true,
+        });
+    }
+
+    write_json(&dir.join(metadata_file), &list)
+}
+
+fn write_videos(
+    dir: &Path,
+    metadata_file: &str,
+    prefix: &str,
+    config: &AssetPackConfig,
+) -> Result<(), AssetError> {
+    let mut list = VideoList::default();
+
+    for i in 1..=config.videos_per_dir {
+        let title = format!("{prefix} Video {i}");
+        let file = dir
+            .join(VIDEOS_DIR)
+            .join(format!("{}.mp4", content_name(&title)));
+        write(&file, blob(config.video_size, i))?;
+        list.videos.push(VideoMetadata {
+            id: 0,
+            title,
+            description: "Synthetic video".to_string(),
+            duration: 0,
+            mime_type: "video/mp4".to_string(),
+            created_at: String::new(),
+        });
+    }
+
+    write_json(&dir.join(metadata_file), &list)
+}
+
+/// Generate the initialization folders of the clients and servers under `root`, overwriting existing files.
+/// Every title and content id is unique across the generated folders. Returns the layout to pass to the initializer
+/// # Errors
+/// Returns an error if a file cannot be written
+pub fn generate_assets(root: &str, config: &AssetPackConfig) -> Result<AssetLayout, AssetError> {
+    let layout = AssetLayout {
+        root: root.to_string(),
+        client_audio: config.client_audio,
+        client_video: config.client_video,
+        servers: config.servers,
+    };
+
+    for i in 0..config.client_audio {
+        let dir = layout.path(AssetKind::ClientAudio, i);
+        let prefix = format!("Audio Client {}", i + 1);
+        write_songs(Path::new(&dir), CLIENT_SONGS_FILE, &prefix, config)?;
+    }
+    for i in 0..config.client_video {
+        let dir = layout.path(AssetKind::ClientVideo, i);
+        let prefix = format!("Video Client {}", i + 1);
+        write_videos(Path::new(&dir), CLIENT_VIDEOS_FILE, &prefix, config)?;
+    }
+    for i in 0..config.servers {
+        let dir = layout.path(AssetKind::Server, i);
+        let prefix = format!("Server {}", i + 1);
+        write_songs(Path::new(&dir), SERVER_SONGS_FILE, &prefix, config)?;
+        write_videos(Path::new(&dir), SERVER_VIDEOS_FILE, &prefix, config)?;
+    }
+
+    assign_content_ids(&layout)?;
+    Ok(layout)
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SongMetadata {
+    pub id: u16,
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+    /// Duration in seconds
+    pub duration: u32,
+    pub image_url: String,
+    pub is_local: bool,
+}
+
+/// Content of `songs_metadata.json` and `init_songs.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SongList {
+    pub songs: Vec<SongMetadata>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VideoMetadata {
+    pub id: u16,
+    pub title: String,
+    pub description: String,
+    /// Duration in seconds
+    pub duration: u32,
+    pub mime_type: String,
+    pub created_at: String,
+}
+
+/// Content of `video_metadata.json` and `init_videos.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VideoList {
+    pub videos: Vec<VideoMetadata>,
+}
+
+pub const CLIENT_SONGS_FILE: &str = "songs_metadata.json";
+pub const CLIENT_VIDEOS_FILE: &str = "video_metadata.json";
+pub const SERVER_SONGS_FILE: &str = "init_songs.json";
+pub const SERVER_VIDEOS_FILE: &str = "init_videos.json";
+pub const PLAYLIST_FILE: &str = "playlist.m3u8";
+pub const SONGS_DIR: &str = "songs";
+pub const VIDEOS_DIR: &str = "videos";
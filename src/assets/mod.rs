@@ -0,0 +1,75 @@
+pub mod check;
+pub mod content_ids;
+pub mod generator;
+pub mod metadata;
+
+use crate::{manifest::NodeManifestEntry, parsed_nodes::NodeType};
+
+/// Layout of the initialization folder of a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetKind {
+    /// `songs_metadata.json` and a `songs/<title>` HLS directory per song
+    ClientAudio,
+    /// `video_metadata.json` and a `videos/<title>.mp4` file per video
+    ClientVideo,
+    /// `init_songs.json`, `init_videos.json` and both the song directories and the video files
+    Server,
+}
+
+impl AssetKind {
+    /// Returns the layout of the folder passed to the node, `None` for drones
+    #[must_use]
+    pub fn of(entry: &NodeManifestEntry) -> Option<Self> {
+        match entry.kind {
+            NodeType::Drone => None,
+            NodeType::Client if entry.implementation == "ClientVideo" => {
+                Some(AssetKind::ClientVideo)
+            }
+            NodeType::Client => Some(AssetKind::ClientAudio),
+            NodeType::Server => Some(AssetKind::Server),
+        }
+    }
+}
+
+/// Name of the song directory or video file of a title, e.g. `Tokyo Cafe` is stored in `tokyocafe`
+#[must_use]
+pub fn content_name(title: &str) -> String {
+    title.to_lowercase().replace(' ', "")
+}
+
+/// Where the initialization folders of the clients and servers are.
+/// The i-th client or server uses the `(i % count) + 1`-th folder of its kind, e.g. `<root>/server/server2`,
+/// so nodes sharing a folder also share its titles and content ids
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetLayout {
+    pub root: String,
+    pub client_audio: usize,
+    pub client_video: usize,
+    pub servers: usize,
+}
+
+impl Default for AssetLayout {
+    /// The folders shipped in `./initialization_files`
+    fn default() -> Self {
+        AssetLayout {
+            root: "./initialization_files".to_string(),
+            client_audio: 3,
+            client_video: 1,
+            servers: 2,
+        }
+    }
+}
+
+impl AssetLayout {
+    /// Returns the folder of the `index`-th node of `kind`, cycling through the available ones
+    #[must_use]
+    pub fn path(&self, kind: AssetKind, index: usize) -> String {
+        let (dir, name, count) = match kind {
+            AssetKind::ClientAudio => ("client_audio", "client", self.client_audio),
+            AssetKind::ClientVideo => ("client_video", "client", self.client_video),
+            AssetKind::Server => ("server", "server", self.servers),
+        };
+        let number = (index % count.max(1)) + 1;
+        format!("{}/{dir}/{name}{number}", self.root)
+    }
+}
@@ -1,7 +1,10 @@
+pub mod analysis;
+pub mod assets;
 mod network_init;
+pub mod simulation;
 mod types;
 mod utils;
 
-pub use network_init::{DroneType, NetworkInitializer};
+pub use network_init::{AssignmentStrategy, DroneType, NetworkInitializer, RestartPolicy};
 pub use types::*;
-pub use utils::errors;
+pub use utils::{capture, errors, event_log, log_sink, parser, topology_generator};
@@ -0,0 +1,173 @@
+use std::env;
+use std::process::ExitCode;
+
+use network_initializer::analysis::lint::lint_topology;
+use network_initializer::analysis::reliability::estimate_reliability;
+use network_initializer::analysis::topology::analyze_topology;
+use network_initializer::analysis::traffic::analyze_traffic;
+use network_initializer::assets::content_ids::assign_content_ids;
+use network_initializer::assets::generator::{generate_assets, AssetPackConfig};
+use network_initializer::assets::AssetLayout;
+use network_initializer::capture::read_capture;
+use network_initializer::parser::Parser;
+
+const USAGE: &str = "Usage:
+    network-initializer show <topology.toml>
+    network-initializer analyze <capture.jsonl> [session_id]
+    network-initializer generate-assets <root> [options]
+    network-initializer assign-content-ids <root> [--client-audio <count>] [--client-video <count>] [--servers <count>]
+
+Options of generate-assets, the defaults are the ones of AssetPackConfig:
+    --client-audio <count>   audio client folders
+    --client-video <count>   video client folders
+    --servers <count>        server folders
+    --songs <count>          songs of every audio client and server
+    --videos <count>         videos of every video client and server
+    --segments <count>       segments of every song
+    --segment-size <bytes>   size of every segment
+    --video-size <bytes>     size of every video";
+
+fn show(args: &[String]) -> Result<(), String> {
+    let [path] = args else {
+        return Err(USAGE.to_string());
+    };
+    let parser = Parser::new(Some(path.as_str())).map_err(|e| e.to_string())?;
+
+    for drone in &parser.drones {
+        println!(
+            "Drone {} pdr: {} connected to {:?}",
+            drone.id, drone.pdr, drone.connected_drone_ids
+        );
+    }
+    for client in &parser.clients {
+        println!(
+            "Client {} connected to {:?}",
+            client.id, client.connected_drone_ids
+        );
+    }
+    for server in &parser.servers {
+        println!(
+            "Server {} connected to {:?}",
+            server.id, server.connected_drone_ids
+        );
+    }
+    print!("{}", analyze_topology(&parser));
+    print!("{}", estimate_reliability(&parser));
+    for warning in lint_topology(&parser) {
+        println!("Warning: {warning}");
+    }
+
+    Ok(())
+}
+
+fn analyze(args: &[String]) -> Result<(), String> {
+    let (path, session_id) = match args {
+        [path] => (path, None),
+        [path, session_id] => (
+            path,
+            Some(
+                session_id
+                    .parse::<u64>()
+                    .map_err(|_| format!("Invalid session id {session_id}"))?,
+            ),
+        ),
+        _ => return Err(USAGE.to_string()),
+    };
+    let packets = read_capture(path).map_err(|e| e.to_string())?;
+    let report = analyze_traffic(&packets);
+
+    match session_id {
+        Some(session_id) => {
+            let mut sessions = report.sessions_with_id(session_id).peekable();
+            if sessions.peek().is_none() {
+                return Err(format!("Session {session_id} not found in {path}"));
+            }
+            for session in sessions {
+                print!("{session}");
+            }
+        }
+        None => print!("{report}"),
+    }
+
+    Ok(())
+}
+
+/// Parse the `--name value` options of the asset commands
+fn asset_pack_config(options: &[String]) -> Result<AssetPackConfig, String> {
+    let mut config = AssetPackConfig::default();
+    for option in options.chunks(2) {
+        let [name, value] = option else {
+            return Err(USAGE.to_string());
+        };
+        let field = match name.as_str() {
+            "--client-audio" => &mut config.client_audio,
+            "--client-video" => &mut config.client_video,
+            "--servers" => &mut config.servers,
+            "--songs" => &mut config.songs_per_dir,
+            "--videos" => &mut config.videos_per_dir,
+            "--segments" => &mut config.segments_per_song,
+            "--segment-size" => &mut config.segment_size,
+            "--video-size" => &mut config.video_size,
+            _ => return Err(USAGE.to_string()),
+        };
+        *field = value
+            .parse()
+            .map_err(|_| format!("Invalid value {value} for {name}"))?;
+    }
+
+    Ok(config)
+}
+
+fn generate(args: &[String]) -> Result<(), String> {
+    let Some((root, options)) = args.split_first() else {
+        return Err(USAGE.to_string());
+    };
+    let config = asset_pack_config(options)?;
+
+    let layout = generate_assets(root, &config).map_err(|e| e.to_string())?;
+    println!(
+        "Generated {} audio client, {} video client and {} server folders in {}",
+        layout.client_audio, layout.client_video, layout.servers, layout.root
+    );
+
+    Ok(())
+}
+
+/// Give unique ids to the contents of existing folders, e.g. after editing them by hand
+fn assign_ids(args: &[String]) -> Result<(), String> {
+    let Some((root, options)) = args.split_first() else {
+        return Err(USAGE.to_string());
+    };
+    let config = asset_pack_config(options)?;
+    let layout = AssetLayout {
+        root: root.clone(),
+        client_audio: config.client_audio,
+        client_video: config.client_video,
+        servers: config.servers,
+    };
+
+    assign_content_ids(&layout).map_err(|e| e.to_string())?;
+    println!("Assigned the content ids of the folders in {root}");
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let res = match args.split_first() {
+        Some((command, args)) if command == "show" => show(args),
+        Some((command, args)) if command == "analyze" => analyze(args),
+        Some((command, args)) if command == "generate-assets" => generate(args),
+        Some((command, args)) if command == "assign-content-ids" => assign_ids(args),
+        _ => Err(USAGE.to_string()),
+    };
+
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1,30 +1,65 @@
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 
 use crossbeam::channel::{Receiver, Sender};
 use wg_internal::{
     controller::{DroneCommand, DroneEvent},
+    drone::Drone,
     network::NodeId,
     packet::Packet,
 };
 
 use crate::{
+    analysis::lint::{lint_topology, LintPolicy, TopologyWarning},
+    assets::{content_ids::DuplicateTitlePolicy, AssetLayout},
+    capture::CaptureWriter,
     channel::Channel,
-    errors::ConfigError,
+    errors::{CaptureError, ConfigError},
+    event_log::{self, ReplayTiming},
+    link_stats::{LinkStats, LinkStatsHandle},
+    log_level::LogLevel,
+    manifest::{ManifestHandle, NodeManifest},
     parsed_nodes::{ParsedClient, ParsedDrone, ParsedServer},
+    simulation_event::SimulationEvent,
     utils::parser::Parser,
 };
 
-use super::{NetworkInitializer, State};
+use super::{
+    links::LinkLayer, net_utils::custom_drone_factory, node_channels::NodeChannels,
+    spawner::DroneSpawner, AssignmentStrategy, DroneType, NetworkInitializer, RestartPolicy, State,
+};
 
 impl NetworkInitializer {
-    /// Set the path of the configuration file
+    /// Set the path of the configuration file, the channels of the nodes and the links are rebuilt from it.
+    /// The seed of the file, if any, replaces the current one
     /// # Errors
-    /// Returns an error if the parser encounters an error
+    /// Returns `ConfigError::AlreadyInitialized` if `get_channels()`, `get_controller_recv()` or
+    /// `get_controller_senders()` has already been called, or an error if the parser encounters an error
     pub fn set_path(&mut self, path: Option<&str>) -> Result<(), ConfigError> {
+        if self.steps_done > 0 {
+            return Err(ConfigError::AlreadyInitialized);
+        }
+
         self.parser = Parser::new(path)?;
+        *self.topology.write().expect("Topology lock poisoned") = self.parser.clone();
+        *self.node_channels.write().expect("Channels lock poisoned") = NodeChannels::default();
+        self.link_layer.reload(&self.parser);
+        if let Some(seed) = self.parser.seed {
+            self.set_seed(seed);
+        }
+        self.channel_map.clear();
+        self.drone_command_map.clear();
+        self.create_channels();
         Ok(())
     }
 
+    /// Add a drone implementation to the built-in ones, e.g. one under development.
+    /// It is selected with `DroneType::Custom(name)` and used by `run_simulation()` and the controller
+    pub fn register_drone<D: Drone + 'static>(&mut self, name: &'static str) {
+        self.custom_drones
+            .push((DroneType::Custom(name), custom_drone_factory::<D>));
+    }
+
     #[must_use]
     pub fn get_nodes(&self) -> (&Vec<ParsedDrone>, &Vec<ParsedClient>, &Vec<ParsedServer>) {
         (
@@ -34,6 +69,150 @@ impl NetworkInitializer {
         )
     }
 
+    /// Set the seed of the simulation, it overrides the one of the configuration file.
+    /// It drives the assignment of the implementations and the link loss and jitter
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.link_layer.set_seed(seed);
+    }
+
+    /// Get the seed of the simulation, randomly chosen if not configured
+    #[must_use]
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn set_assignment_strategy(&mut self, strategy: AssignmentStrategy) {
+        self.assignment_strategy = strategy;
+    }
+
+    /// Set the log level of every client and server, it overrides the one of the configuration file
+    pub fn set_log_level(&mut self, level: LogLevel) {
+        self.parser.log_level = Some(level);
+    }
+
+    /// Set the log level of a single client or server
+    pub fn set_node_log_level(&mut self, id: NodeId, level: LogLevel) {
+        self.log_levels.insert(id, level);
+    }
+
+    /// Set the folders passed to the clients and servers, e.g. the ones created by `generate_assets`
+    pub fn set_asset_layout(&mut self, layout: AssetLayout) {
+        self.asset_layout = layout;
+    }
+
+    /// Set what happens when two nodes would publish the same title or content id, by default a warning is printed
+    pub fn set_duplicate_title_policy(&mut self, policy: DuplicateTitlePolicy) {
+        self.duplicate_title_policy = policy;
+    }
+
+    /// Set whether the topology warnings stop the simulation from starting, by default they are only printed
+    pub fn set_lint_policy(&mut self, policy: LintPolicy) {
+        self.lint_policy = policy;
+    }
+
+    /// Get the single points of failure of the topology
+    #[must_use]
+    pub fn lint(&self) -> Vec<TopologyWarning> {
+        lint_topology(&self.parser)
+    }
+
+    /// Set the restart policy used for the drones without a specific one
+    pub fn set_restart_policy(&mut self, policy: RestartPolicy) {
+        self.default_restart_policy = policy;
+    }
+
+    /// Set the restart policy of a single drone
+    pub fn set_node_restart_policy(&mut self, id: NodeId, policy: RestartPolicy) {
+        self.restart_policies.insert(id, policy);
+    }
+
+    /// Count the packets going through every link and track their queue length.
+    /// Must be called before `run_simulation()`
+    pub fn enable_link_stats(&mut self) {
+        self.link_layer.enable_stats();
+    }
+
+    /// Write every packet exchanged between the nodes to `path`, one JSON line per packet.
+    /// Must be called before `run_simulation()`, the file can be loaded with `capture::read_capture()`
+    /// # Errors
+    /// Returns an error if the capture file cannot be created
+    pub fn enable_capture(&mut self, path: &str) -> Result<(), CaptureError> {
+        self.link_layer.set_capture(CaptureWriter::create(path)?);
+        Ok(())
+    }
+
+    /// Record every event sent to the controller to `path`, one JSON line per event.
+    /// Must be called before `get_controller_recv()`, which then returns the recorded events
+    /// # Errors
+    /// Returns an error if the event log cannot be created
+    pub fn record_events(&mut self, path: &str) -> Result<(), CaptureError> {
+        self.node_event.receiver =
+            event_log::record_events(self.node_event.receiver.clone(), path)?;
+        Ok(())
+    }
+
+    /// Feed the events recorded in `path` to the controller receiver, as if the network was running
+    /// # Errors
+    /// Returns an error if the event log cannot be loaded
+    pub fn replay_events(&self, path: &str, timing: ReplayTiming) -> Result<(), CaptureError> {
+        event_log::replay_events(path, self.node_event.sender.clone(), timing)
+    }
+
+    /// Get the sender `from` must use to reach `to` through the link layer, as the nodes do, so that
+    /// link stats, capture, latency and loss also apply to nodes driven from the channels of `get_channels()`.
+    /// Returns `None` if `to` does not exist or `run_simulation()` has already consumed the channels
+    #[must_use]
+    pub fn get_link_sender(&self, from: NodeId, to: NodeId) -> Option<Sender<Packet>> {
+        let channel = self.channel_map.get(&to)?;
+        Some(self.link_layer.connect(from, to, &channel.sender))
+    }
+
+    /// Get a snapshot of the traffic of every link, empty if the link stats are not enabled
+    #[must_use]
+    pub fn link_stats(&self) -> Vec<LinkStats> {
+        self.link_layer
+            .stats()
+            .map(LinkStatsHandle::snapshot)
+            .unwrap_or_default()
+    }
+
+    /// Get a handle to the link stats that can be read from another thread while the simulation is running
+    #[must_use]
+    pub fn get_link_stats_handle(&self) -> Option<LinkStatsHandle> {
+        self.link_layer.stats().cloned()
+    }
+
+    /// Get the mapping between every node and the implementation running it
+    /// # Note
+    /// Returns `None` until `run_simulation()` has instantiated the nodes
+    #[must_use]
+    pub fn get_manifest(&self) -> Option<NodeManifest> {
+        self.manifest.get()
+    }
+
+    /// Get a handle to the manifest that can be read from another thread while the simulation is running
+    #[must_use]
+    pub fn get_manifest_handle(&self) -> ManifestHandle {
+        self.manifest.clone()
+    }
+
+    pub(crate) fn get_link_layer(&self) -> LinkLayer {
+        self.link_layer.clone()
+    }
+
+    pub(crate) fn get_drone_spawner(&self) -> DroneSpawner {
+        DroneSpawner::new(self)
+    }
+
+    pub(crate) fn get_topology_handle(&self) -> Arc<RwLock<Parser>> {
+        Arc::clone(&self.topology)
+    }
+
+    pub(crate) fn get_node_channels(&self) -> Arc<RwLock<NodeChannels>> {
+        Arc::clone(&self.node_channels)
+    }
+
     fn switch_state(&mut self) {
         self.steps_done += 1;
         if self.steps_done == 3 {
@@ -47,6 +226,17 @@ impl NetworkInitializer {
         self.node_event.receiver.clone()
     }
 
+    /// Get the receiver of the events generated by the initializer, such as node panics.
+    /// Once a `SimulationController` is created these events are delivered by its `events()` instead
+    #[must_use]
+    pub fn get_simulation_recv(&self) -> Receiver<SimulationEvent> {
+        self.simulation_event.receiver.clone()
+    }
+
+    pub(crate) fn get_simulation_sender(&self) -> Sender<SimulationEvent> {
+        self.simulation_event.sender.clone()
+    }
+
     #[must_use]
     pub fn get_controller_senders(&mut self) -> HashMap<NodeId, Sender<DroneCommand>> {
         self.switch_state();
@@ -0,0 +1,387 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossbeam::channel::{bounded, unbounded, Receiver, Sender, TrySendError};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use wg_internal::{
+    network::NodeId,
+    packet::{Packet, PacketType},
+};
+
+use crate::{
+    channel::OverflowPolicy,
+    link_stats::{LinkCounters, LinkStatsHandle},
+    parsed_links::{LossModel, ParsedLink},
+    simulation_event::{LinkDropCause, SimulationEvent},
+    utils::capture::{CaptureWriter, CapturedPacket},
+    utils::parser::Parser,
+};
+
+/// Timing of a single direction of a link
+#[derive(Debug, Clone, Copy)]
+struct LinkDelay {
+    latency: Duration,
+    jitter: Duration,
+    bandwidth_kbps: Option<u64>,
+}
+
+impl From<&ParsedLink> for LinkDelay {
+    fn from(link: &ParsedLink) -> Self {
+        LinkDelay {
+            latency: Duration::from_millis(link.latency_ms),
+            jitter: Duration::from_millis(link.jitter_ms),
+            bandwidth_kbps: link.bandwidth_kbps,
+        }
+    }
+}
+
+/// Current state of the loss model of a single direction of a link
+struct LinkLoss {
+    model: LossModel,
+    bad_state: bool,
+}
+
+impl LinkLoss {
+    fn new(model: LossModel) -> Self {
+        LinkLoss {
+            model,
+            bad_state: false,
+        }
+    }
+
+    /// Only fragments are lost, as drones do with their PDR
+    fn is_lost(&mut self, packet: &Packet, rng: &mut StdRng) -> bool {
+        if !matches!(packet.pack_type, PacketType::MsgFragment(_)) {
+            return false;
+        }
+
+        let probability = match self.model {
+            LossModel::Uniform { probability } => probability,
+            LossModel::GilbertElliott {
+                p_good_to_bad,
+                p_bad_to_good,
+                loss_good,
+                loss_bad,
+            } => {
+                let transition = if self.bad_state {
+                    p_bad_to_good
+                } else {
+                    p_good_to_bad
+                };
+                if rng.gen::<f64>() < transition {
+                    self.bad_state = !self.bad_state;
+                }
+
+                if self.bad_state {
+                    loss_bad
+                } else {
+                    loss_good
+                }
+            }
+        };
+
+        rng.gen::<f64>() < probability
+    }
+}
+
+/// What a single direction of a link does to the packets crossing it
+#[derive(Debug, Clone, Copy, Default)]
+struct LinkProperties {
+    delay: Option<LinkDelay>,
+    loss: Option<LossModel>,
+}
+
+impl From<&ParsedLink> for LinkProperties {
+    fn from(link: &ParsedLink) -> Self {
+        let delay = LinkDelay::from(link);
+        let has_delay =
+            !delay.latency.is_zero() || !delay.jitter.is_zero() || delay.bandwidth_kbps.is_some();
+
+        LinkProperties {
+            delay: has_delay.then_some(delay),
+            loss: link.loss,
+        }
+    }
+}
+
+impl LinkDelay {
+    fn latency_with_jitter(&self, rng: &mut StdRng) -> Duration {
+        if self.jitter.is_zero() {
+            return self.latency;
+        }
+
+        let jitter = rng.gen_range(Duration::ZERO..=self.jitter * 2);
+        (self.latency + jitter).saturating_sub(self.jitter)
+    }
+
+    /// Time needed to put the packet on the link, only fragment payloads are accounted
+    fn transmission_time(&self, packet: &Packet) -> Duration {
+        match (self.bandwidth_kbps, &packet.pack_type) {
+            (Some(bandwidth_kbps), PacketType::MsgFragment(fragment)) => {
+                Duration::from_micros(u64::from(fragment.length) * 8 * 1000 / bandwidth_kbps)
+            }
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+/// Layer between the senders given to a node and the receivers of its neighbours
+#[derive(Debug, Clone)]
+pub(crate) struct LinkLayer {
+    overflow_policy: OverflowPolicy,
+    simulation_send: Sender<SimulationEvent>,
+    stats: Option<LinkStatsHandle>,
+    properties: HashMap<(NodeId, NodeId), LinkProperties>,
+    seed: u64,
+    capture: Option<CaptureWriter>,
+}
+
+impl LinkLayer {
+    pub(crate) fn new(parser: &Parser, simulation_send: Sender<SimulationEvent>) -> Self {
+        let properties = parser
+            .links
+            .iter()
+            .flat_map(|link| {
+                let properties = LinkProperties::from(link);
+                [
+                    ((link.from, link.to), properties),
+                    ((link.to, link.from), properties),
+                ]
+            })
+            .collect();
+
+        LinkLayer {
+            overflow_policy: parser.overflow_policy,
+            simulation_send,
+            stats: None,
+            properties,
+            seed: 0,
+            capture: None,
+        }
+    }
+
+    /// Take the link properties and the overflow policy of another topology, keeping the stats and the capture
+    pub(crate) fn reload(&mut self, parser: &Parser) {
+        let reloaded = LinkLayer::new(parser, self.simulation_send.clone());
+        self.overflow_policy = reloaded.overflow_policy;
+        self.properties = reloaded.properties;
+    }
+
+    pub(crate) fn enable_stats(&mut self) {
+        self.stats.get_or_insert_with(LinkStatsHandle::default);
+    }
+
+    pub(crate) fn stats(&self) -> Option<&LinkStatsHandle> {
+        self.stats.as_ref()
+    }
+
+    pub(crate) fn set_capture(&mut self, capture: CaptureWriter) {
+        self.capture = Some(capture);
+    }
+
+    pub(crate) fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    /// Every direction of every link draws from its own generator derived from the simulation seed
+    fn rng(&self, from: NodeId, to: NodeId) -> StdRng {
+        StdRng::seed_from_u64(self.seed ^ ((u64::from(from) << 8) | u64::from(to)))
+    }
+
+    fn is_transparent(&self, from: NodeId, to: NodeId) -> bool {
+        self.overflow_policy == OverflowPolicy::Block
+            && self.stats.is_none()
+            && self.capture.is_none()
+            && !self.properties.contains_key(&(from, to))
+    }
+
+    /// Returns the sender `from` must use to reach `to`, whose channel is `to_send`.
+    /// When the link has something to do with the packets a forwarding thread is placed in between,
+    /// otherwise `to_send` is used directly.
+    pub(crate) fn connect(
+        &self,
+        from: NodeId,
+        to: NodeId,
+        to_send: &Sender<Packet>,
+    ) -> Sender<Packet> {
+        if self.is_transparent(from, to) {
+            return to_send.clone();
+        }
+
+        // A bounded destination must not be hidden behind an unbounded link,
+        // zero capacity makes the node wait until the packet is taken by the link
+        let (link_send, link_recv) = match to_send.capacity() {
+            Some(_) => bounded::<Packet>(0),
+            None => unbounded::<Packet>(),
+        };
+        let link = Link {
+            from,
+            to,
+            to_send: to_send.clone(),
+            overflow_policy: self.overflow_policy,
+            simulation_send: self.simulation_send.clone(),
+            counters: self.stats.as_ref().map(|stats| stats.counters(from, to)),
+            capture: self.capture.clone(),
+        };
+
+        let properties = self
+            .properties
+            .get(&(from, to))
+            .copied()
+            .unwrap_or_default();
+        let loss = properties.loss.map(LinkLoss::new);
+        let rng = self.rng(from, to);
+
+        match properties.delay {
+            Some(delay) => link.spawn_delayed(link_recv, delay, loss, rng),
+            None => link.spawn(link_recv, loss, rng),
+        }
+
+        link_send
+    }
+}
+
+/// Forwarding side of a link
+struct Link {
+    from: NodeId,
+    to: NodeId,
+    to_send: Sender<Packet>,
+    overflow_policy: OverflowPolicy,
+    simulation_send: Sender<SimulationEvent>,
+    counters: Option<Arc<LinkCounters>>,
+    capture: Option<CaptureWriter>,
+}
+
+impl Link {
+    fn record_packet(&self, packet: &Packet) {
+        if let Some(counters) = &self.counters {
+            counters.record_packet(packet);
+        }
+    }
+
+    fn record_queue_len(&self, link_len: usize) {
+        if let Some(counters) = &self.counters {
+            counters.record_queue_len(link_len + self.to_send.len());
+        }
+    }
+
+    /// The dropped packet is reported as a link event, so that it is not mistaken for a drone drop
+    fn drop_packet(&self, packet: &Packet, cause: LinkDropCause) {
+        if let Some(counters) = &self.counters {
+            counters.record_drop();
+        }
+        let _ = self.simulation_send.send(SimulationEvent::LinkDropped {
+            from: self.from,
+            to: self.to,
+            session_id: packet.session_id,
+            cause,
+        });
+    }
+
+    /// Returns the packet if it survives the link
+    fn admit(
+        &self,
+        packet: Packet,
+        loss: &mut Option<LinkLoss>,
+        rng: &mut StdRng,
+    ) -> Option<Packet> {
+        self.record_packet(&packet);
+        let lost = loss.as_mut().is_some_and(|loss| loss.is_lost(&packet, rng));
+
+        if let Some(capture) = &self.capture {
+            let mut captured = CapturedPacket::new(self.from, self.to, &packet);
+            captured.lost_on_link = lost;
+            capture.write(captured);
+        }
+
+        if lost {
+            self.drop_packet(&packet, LinkDropCause::Loss);
+            return None;
+        }
+
+        Some(packet)
+    }
+
+    /// Returns `false` if the receiving node is disconnected
+    fn deliver(&self, packet: Packet) -> bool {
+        match self.overflow_policy {
+            OverflowPolicy::Block => self.to_send.send(packet).is_ok(),
+            OverflowPolicy::Drop => match self.to_send.try_send(packet) {
+                Ok(()) => true,
+                Err(TrySendError::Full(packet)) => {
+                    self.drop_packet(&packet, LinkDropCause::Overflow);
+                    true
+                }
+                Err(TrySendError::Disconnected(_)) => false,
+            },
+        }
+    }
+
+    fn spawn(self, link_recv: Receiver<Packet>, mut loss: Option<LinkLoss>, mut rng: StdRng) {
+        thread::spawn(move || {
+            for packet in &link_recv {
+                let Some(packet) = self.admit(packet, &mut loss, &mut rng) else {
+                    continue;
+                };
+
+                // The neighbour is gone, dropping `link_recv` makes the node see it
+                if !self.deliver(packet) {
+                    break;
+                }
+
+                self.record_queue_len(link_recv.len());
+            }
+        });
+    }
+
+    /// Timestamps the packets as soon as they are sent and delivers them once their delay is elapsed.
+    /// Two threads are used so that a packet waiting for delivery does not delay the following ones.
+    fn spawn_delayed(
+        self,
+        link_recv: Receiver<Packet>,
+        delay: LinkDelay,
+        mut loss: Option<LinkLoss>,
+        mut rng: StdRng,
+    ) {
+        let (delayed_send, delayed_recv) = unbounded::<(Instant, Packet)>();
+        let link = Arc::new(self);
+        // Only read to count the packets not yet timestamped, as the non delayed link does
+        let pending = link_recv.clone();
+
+        let sending_link = Arc::clone(&link);
+        thread::spawn(move || {
+            let mut line_free_at = Instant::now();
+            let mut last_due = Instant::now();
+
+            for packet in &link_recv {
+                let Some(packet) = sending_link.admit(packet, &mut loss, &mut rng) else {
+                    continue;
+                };
+
+                // The packet waits for the previous ones to be transmitted, then crosses the link
+                let now = Instant::now();
+                line_free_at = line_free_at.max(now) + delay.transmission_time(&packet);
+                let due = (line_free_at + delay.latency_with_jitter(&mut rng)).max(last_due);
+                last_due = due;
+
+                if delayed_send.send((due, packet)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        thread::spawn(move || {
+            for (due, packet) in &delayed_recv {
+                thread::sleep(due.saturating_duration_since(Instant::now()));
+
+                if !link.deliver(packet) {
+                    break;
+                }
+
+                link.record_queue_len(pending.len() + delayed_recv.len());
+            }
+        });
+    }
+}
@@ -1,6 +1,15 @@
 mod getters;
+pub(crate) mod links;
 mod net_utils;
-
+pub(crate) mod node_channels;
+pub(crate) mod spawner;
+mod supervisor;
+
+use crate::analysis::lint::{lint_topology, LintPolicy};
+use crate::apply_log_level;
+use crate::assets::check::check_manifest_assets;
+use crate::assets::content_ids::{find_duplicate_ids, find_duplicate_titles, DuplicateTitlePolicy};
+use crate::assets::{AssetKind, AssetLayout};
 use crate::create_drone_factories;
 use crate::parsed_nodes::ParsedClient;
 use crate::parsed_nodes::ParsedDrone;
@@ -10,21 +19,32 @@ use crate::utils;
 
 use client_audio::ClientAudio;
 use client_video::ClientVideo;
-use crossbeam::channel::{unbounded, Receiver, Sender};
+use crossbeam::channel::{Receiver, Sender};
 #[cfg(feature = "use_ctrlc")]
 use ctrlc;
+use links::LinkLayer;
 use net_utils::BoxClient;
 use net_utils::BoxDrone;
+use node_channels::NodeChannels;
 use packet_forge::ClientT;
 use packet_forge::ClientType;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use server::Server;
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::sync::{Arc, RwLock};
 use std::thread::{self, JoinHandle};
+use supervisor::{supervise_drone, supervise_node, DroneRespawner};
 use types::channel::Channel;
-use types::parsed_nodes::Initializable;
+use types::log_level::LogLevel;
+use types::manifest::{ManifestHandle, NodeManifest, NodeManifestEntry};
+use types::parsed_nodes::{Initializable, NodeType};
+use types::simulation_event::SimulationEvent;
 use utils::errors::ConfigError;
+use utils::log_sink::{log_as, log_error_as, log_warning_as};
 use utils::parser::Parser;
+use utils::topology_generator::{generate_topology, TopologyConfig};
 use wg_internal::controller::{DroneCommand, DroneEvent};
 use wg_internal::drone::Drone;
 use wg_internal::network::NodeId;
@@ -44,14 +64,12 @@ use rusty_drones::RustyDrone;
 use skylink::SkyLinkDrone;
 use wg_2024_rust::drone::RustDrone;
 
+pub use supervisor::RestartPolicy;
+
 type GenericDrone = Box<dyn Drone>;
 type GenericClient = Box<dyn ClientT>;
 
-const CLIENT_AUDIO_CONFIGURATIONS_NUM: usize = 3;
-const CLIENT_VIDEO_CONFIGURATIONS_NUM: usize = 3;
-const SERVER_CONFIGURATIONS_NUM: usize = 2;
-
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DroneType {
     RustezeDrone,
     DrOnes,
@@ -64,6 +82,107 @@ pub enum DroneType {
     SkyLinkDrone,
     RustyDrone,
     NullPointerDrone,
+    /// Implementation added with `NetworkInitializer::register_drone()`
+    Custom(&'static str),
+}
+
+impl DroneType {
+    /// Name of the implementation, e.g. `RustRoveri`
+    #[must_use]
+    pub fn name(&self) -> String {
+        match self {
+            DroneType::Custom(name) => (*name).to_string(),
+            _ => format!("{self:?}"),
+        }
+    }
+}
+
+/// How the available implementations are assigned to the nodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AssignmentStrategy {
+    /// The i-th node uses the i-th implementation, cycling through them
+    #[default]
+    RoundRobin,
+    /// Every node uses an implementation drawn from the simulation seed
+    Random,
+}
+
+impl AssignmentStrategy {
+    /// Returns the index of the factory used by each of the `nodes` nodes
+    fn assign(self, nodes: usize, factories: usize, rng: &mut StdRng) -> Vec<usize> {
+        match self {
+            AssignmentStrategy::RoundRobin => (0..nodes).map(|i| i % factories).collect(),
+            AssignmentStrategy::Random => (0..nodes).map(|_| rng.gen_range(0..factories)).collect(),
+        }
+    }
+}
+
+/// Returns the factory of every available drone implementation
+fn drone_factories() -> Vec<(DroneType, BoxDrone)> {
+    // Use the macro to generate factories mapped to DroneType
+    let mut drone_factories: Vec<(DroneType, BoxDrone)> = Vec::new();
+
+    #[cfg(feature = "use_rusteze_drone")]
+    {
+        drone_factories.extend(create_drone_factories!(RustezeDrone));
+    }
+
+    drone_factories.extend(create_drone_factories!(
+        DrOnes,
+        RustBustersDrone,
+        RustDrone,
+        RustRoveri,
+        RustDoIt,
+        LockheedRustin,
+        CppEnjoyersDrone,
+        SkyLinkDrone,
+        RustyDrone,
+        NullPointerDrone
+    ));
+
+    drone_factories
+}
+
+/// Returns the factory of every available client implementation
+fn client_factories() -> Vec<(ClientType, BoxClient)> {
+    vec![
+        (
+            ClientType::Song,
+            Box::new(
+                |client: &ParsedClient,
+                 command_send: Sender<DroneEvent>,
+                 command_recv: Receiver<DroneCommand>,
+                 senders: HashMap<u8, Sender<Packet>>,
+                 receiver: Receiver<Packet>| {
+                    Box::new(ClientAudio::new(
+                        client.id,
+                        command_send,
+                        command_recv,
+                        receiver,
+                        senders,
+                    )) as Box<dyn ClientT>
+                },
+            ) as BoxClient,
+        ),
+        (
+            ClientType::Video,
+            Box::new(
+                |client: &ParsedClient,
+                 command_send: Sender<DroneEvent>,
+                 command_recv: Receiver<DroneCommand>,
+                 senders: HashMap<u8, Sender<Packet>>,
+                 receiver: Receiver<Packet>| {
+                    Box::new(ClientVideo::new(
+                        client.id,
+                        command_send,
+                        command_recv,
+                        receiver,
+                        senders,
+                    )) as Box<dyn ClientT>
+                },
+            ) as BoxClient,
+        ),
+    ]
 }
 
 #[derive(Debug)]
@@ -85,6 +204,28 @@ pub struct NetworkInitializer {
     drone_command_map: HashMap<NodeId, Channel<DroneCommand>>,
     // channel from drones to controller
     node_event: Channel<DroneEvent>,
+    // stages applied to the packets travelling between nodes
+    link_layer: LinkLayer,
+    // topology and senders of the running network, shared with the controller and the supervisors
+    topology: Arc<RwLock<Parser>>,
+    node_channels: Arc<RwLock<NodeChannels>>,
+    // drone implementations added to the built-in ones
+    custom_drones: Vec<(DroneType, fn() -> BoxDrone)>,
+    // implementation chosen for each node, set by initialize_network
+    manifest: ManifestHandle,
+    // events generated by the initializer (e.g. node panics)
+    simulation_event: Channel<SimulationEvent>,
+    default_restart_policy: RestartPolicy,
+    restart_policies: HashMap<NodeId, RestartPolicy>,
+    // log levels set through the builder, they override the configuration file
+    log_levels: HashMap<NodeId, LogLevel>,
+    // folders passed to the clients and servers
+    asset_layout: AssetLayout,
+    duplicate_title_policy: DuplicateTitlePolicy,
+    lint_policy: LintPolicy,
+    // seed of every random decision of the initializer
+    seed: u64,
+    assignment_strategy: AssignmentStrategy,
 }
 
 impl NetworkInitializer {
@@ -92,45 +233,107 @@ impl NetworkInitializer {
     /// # Errors
     /// Returns an error if parser encounters an error
     pub fn new(path: Option<&str>) -> Result<Self, ConfigError> {
-        let parser = Parser::new(path)?;
+        Ok(NetworkInitializer::with_parser(Parser::new(path)?))
+    }
+
+    /// Create a new configuration on a topology generated from the seed, which also seeds the simulation
+    /// # Errors
+    /// Returns an error if the topology cannot be generated
+    pub fn generate(config: &TopologyConfig, seed: u64) -> Result<Self, ConfigError> {
+        Ok(NetworkInitializer::with_parser(generate_topology(
+            config, seed,
+        )?))
+    }
+
+    fn with_parser(parser: Parser) -> Self {
+        let seed = parser.seed.unwrap_or_else(rand::random);
+        let node_event = Channel::default();
+        let simulation_event = Channel::default();
+        let mut link_layer = LinkLayer::new(&parser, simulation_event.sender.clone());
+        link_layer.set_seed(seed);
 
         let mut net_init = NetworkInitializer {
             state: State::Instantiated,
             steps_done: 0,
+            topology: Arc::new(RwLock::new(parser.clone())),
+            node_channels: Arc::default(),
+            custom_drones: Vec::new(),
             parser,
             channel_map: HashMap::new(),
             drone_command_map: HashMap::new(),
-            node_event: Channel::new(unbounded().0, unbounded().1),
+            node_event,
+            link_layer,
+            manifest: ManifestHandle::default(),
+            simulation_event,
+            default_restart_policy: RestartPolicy::default(),
+            restart_policies: HashMap::new(),
+            log_levels: HashMap::new(),
+            asset_layout: AssetLayout::default(),
+            duplicate_title_policy: DuplicateTitlePolicy::default(),
+            lint_policy: LintPolicy::default(),
+            seed,
+            assignment_strategy: AssignmentStrategy::default(),
         };
 
         net_init.create_channels();
-        Ok(net_init)
+        net_init
+    }
+
+    /// Returns the built-in drone factories followed by the registered ones
+    fn drone_factories(&self) -> Vec<(DroneType, BoxDrone)> {
+        let mut factories = drone_factories();
+        factories.extend(
+            self.custom_drones
+                .iter()
+                .map(|(drone_type, factory)| (*drone_type, factory())),
+        );
+        factories
     }
 
     fn create_channels(&mut self) {
         for drone in &self.parser.drones {
-            self.channel_map.insert(drone.id, Channel::default());
+            let capacity = self.parser.channel_capacity(drone.id);
+            self.channel_map
+                .insert(drone.id, Channel::with_capacity(capacity));
             self.drone_command_map.insert(drone.id, Channel::default());
         }
         for client in &self.parser.clients {
-            self.channel_map.insert(client.id, Channel::default());
+            let capacity = self.parser.channel_capacity(client.id);
+            self.channel_map
+                .insert(client.id, Channel::with_capacity(capacity));
             self.drone_command_map.insert(client.id, Channel::default());
         }
         for server in &self.parser.servers {
-            self.channel_map.insert(server.id, Channel::default());
+            let capacity = self.parser.channel_capacity(server.id);
+            self.channel_map
+                .insert(server.id, Channel::with_capacity(capacity));
             self.drone_command_map.insert(server.id, Channel::default());
         }
 
-        self.node_event = Channel::default();
+        let mut node_channels = self.node_channels.write().expect("Channels lock poisoned");
+        for (id, channel) in &self.channel_map {
+            node_channels
+                .packet_senders
+                .insert(*id, channel.sender.clone());
+        }
+        for (id, channel) in &self.drone_command_map {
+            node_channels
+                .command_senders
+                .insert(*id, channel.sender.clone());
+        }
     }
 
-    fn initialize_entities<T, F, O>(
+    #[allow(clippy::too_many_arguments)]
+    fn initialize_entities<'f, T, K, F, O>(
         nodes: &[T],
         channel_map: &HashMap<NodeId, Channel<Packet>>,
         channel_command_map: &HashMap<NodeId, Channel<DroneCommand>>,
         node_event: &Channel<DroneEvent>,
-        create_entity: &[F],
-    ) -> Vec<O>
+        link_layer: &LinkLayer,
+        link_senders: &mut HashMap<(NodeId, NodeId), Sender<Packet>>,
+        create_entity: &'f [(K, F)],
+        assignment: &[usize],
+    ) -> Vec<(&'f K, O)>
     where
         T: Initializable,
         F: Fn(
@@ -149,7 +352,9 @@ impl NetworkInitializer {
 
                 for neighbor_id in node.connected_drone_ids() {
                     if let Some(channel) = channel_map.get(neighbor_id) {
-                        senders.insert(*neighbor_id, channel.sender.clone());
+                        let sender = link_layer.connect(*node.id(), *neighbor_id, &channel.sender);
+                        link_senders.insert((*node.id(), *neighbor_id), sender.clone());
+                        senders.insert(*neighbor_id, sender);
                     }
                 }
 
@@ -166,111 +371,178 @@ impl NetworkInitializer {
                 let command_send = node_event.sender.clone();
 
                 // Use the current new method to create the entity
-                let create_entity = &create_entity[index % create_entity.len()];
+                let (kind, create_entity) = &create_entity[assignment[index]];
 
-                create_entity(node, command_send, command_recv, senders, receiver)
+                (
+                    kind,
+                    create_entity(node, command_send, command_recv, senders, receiver),
+                )
             })
             .collect()
     }
 
     fn filter_nodes<T: PartialEq, G>(
-        selected_nodes: Option<Vec<T>>,
+        selected_nodes: Option<&[T]>,
         node_factories: Vec<(T, G)>,
-    ) -> Vec<G> {
+    ) -> Vec<(T, G)> {
         // Filter factories based on the selected drones
         if let Some(selected) = selected_nodes {
             node_factories
                 .into_iter()
                 .filter(|(drone_type, _)| selected.contains(drone_type))
-                .map(|(_, factory)| factory)
                 .collect()
         } else {
-            node_factories
-                .into_iter()
-                .map(|(_, factory)| factory)
-                .collect() // Use all factories if no selection is provided
+            node_factories // Use all factories if no selection is provided
         }
     }
 
+    /// Returns the index of the factory used by every drone and client.
+    /// Every random decision of the initializer derives from the seed
+    fn assign(&self, drone_factories: usize, client_factories: usize) -> (Vec<usize>, Vec<usize>) {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+
+        let drone_assignment =
+            self.assignment_strategy
+                .assign(self.parser.drones.len(), drone_factories, &mut rng);
+        let client_assignment =
+            self.assignment_strategy
+                .assign(self.parser.clients.len(), client_factories, &mut rng);
+
+        (drone_assignment, client_assignment)
+    }
+
+    /// Record which implementation was chosen for each node
+    fn build_manifest(
+        &self,
+        drone_types: &[DroneType],
+        client_types: &[&ClientType],
+    ) -> NodeManifest {
+        let mut manifest = NodeManifest {
+            seed: self.seed,
+            nodes: Vec::new(),
+            partition: None,
+        };
+
+        for (drone, drone_type) in self.parser.drones.iter().zip(drone_types) {
+            manifest.nodes.push(NodeManifestEntry {
+                id: drone.id,
+                kind: NodeType::Drone,
+                implementation: drone_type.name(),
+                pdr: Some(drone.pdr),
+                neighbours: drone.connected_drone_ids.clone(),
+                asset_path: None,
+            });
+        }
+
+        for (i, (client, client_type)) in self.parser.clients.iter().zip(client_types).enumerate() {
+            // Determine the path based on the client type
+            let (implementation, init_file_path) = if **client_type == ClientType::Video {
+                (
+                    "ClientVideo",
+                    self.asset_layout.path(AssetKind::ClientVideo, i),
+                )
+            } else {
+                (
+                    "ClientAudio",
+                    self.asset_layout.path(AssetKind::ClientAudio, i),
+                )
+            };
+
+            manifest.nodes.push(NodeManifestEntry {
+                id: client.id,
+                kind: NodeType::Client,
+                implementation: implementation.to_string(),
+                pdr: None,
+                neighbours: client.connected_drone_ids.clone(),
+                asset_path: Some(init_file_path),
+            });
+        }
+
+        for (i, server) in self.parser.servers.iter().enumerate() {
+            manifest.nodes.push(NodeManifestEntry {
+                id: server.id,
+                kind: NodeType::Server,
+                implementation: "Server".to_string(),
+                pdr: None,
+                neighbours: server.connected_drone_ids.clone(),
+                asset_path: Some(self.asset_layout.path(AssetKind::Server, i)),
+            });
+        }
+
+        manifest
+    }
+
+    /// Get the manifest `run_simulation()` would record with the same arguments, without instantiating any node
+    #[must_use]
+    pub fn plan_manifest(
+        &self,
+        selected_drones: Option<Vec<DroneType>>,
+        selected_clients: Option<Vec<ClientType>>,
+    ) -> NodeManifest {
+        self.plan(selected_drones.as_deref(), selected_clients.as_deref())
+    }
+
+    fn plan(
+        &self,
+        selected_drones: Option<&[DroneType]>,
+        selected_clients: Option<&[ClientType]>,
+    ) -> NodeManifest {
+        let drone_types: Vec<DroneType> =
+            Self::filter_nodes(selected_drones, self.drone_factories())
+                .into_iter()
+                .map(|(drone_type, _)| drone_type)
+                .collect();
+        let client_types: Vec<ClientType> =
+            Self::filter_nodes(selected_clients, client_factories())
+                .into_iter()
+                .map(|(client_type, _)| client_type)
+                .collect();
+
+        let (drone_assignment, client_assignment) =
+            self.assign(drone_types.len(), client_types.len());
+        let drone_types: Vec<DroneType> =
+            drone_assignment.iter().map(|i| drone_types[*i]).collect();
+        let client_types: Vec<&ClientType> = client_assignment
+            .iter()
+            .map(|i| &client_types[*i])
+            .collect();
+
+        self.build_manifest(&drone_types, &client_types)
+    }
+
     /// Returns all the instances of the needed nodes
     /// ### Arguments
     /// - `selected_drones`: if None uses all drones otherwise uses only the selected ones
     /// - `selected_clients`: if None uses all clients otherwise uses only the selected ones
     fn initialize_network(
         &mut self,
-        selected_drones: Option<Vec<DroneType>>,
-        selected_clients: Option<Vec<ClientType>>,
-    ) -> (Vec<GenericDrone>, Vec<GenericClient>, Vec<Server>) {
-        // Use the macro to generate factories mapped to DroneType
-        let mut drone_factories: Vec<(DroneType, BoxDrone)> = Vec::new();
-
-        #[cfg(feature = "use_rusteze_drone")]
-        {
-            drone_factories.extend(create_drone_factories!(RustezeDrone));
-        }
-
-        drone_factories.extend(create_drone_factories!(
-            DrOnes,
-            RustBustersDrone,
-            RustDrone,
-            RustRoveri,
-            RustDoIt,
-            LockheedRustin,
-            CppEnjoyersDrone,
-            SkyLinkDrone,
-            RustyDrone,
-            NullPointerDrone
-        ));
-
-        let client_factories: Vec<(ClientType, BoxClient)> = vec![
-            (
-                ClientType::Song,
-                Box::new(
-                    |client: &ParsedClient,
-                     command_send: Sender<DroneEvent>,
-                     command_recv: Receiver<DroneCommand>,
-                     senders: HashMap<u8, Sender<Packet>>,
-                     receiver: Receiver<Packet>| {
-                        Box::new(ClientAudio::new(
-                            client.id,
-                            command_send,
-                            command_recv,
-                            receiver,
-                            senders,
-                        )) as Box<dyn ClientT>
-                    },
-                ) as BoxClient,
-            ),
-            (
-                ClientType::Video,
-                Box::new(
-                    |client: &ParsedClient,
-                     command_send: Sender<DroneEvent>,
-                     command_recv: Receiver<DroneCommand>,
-                     senders: HashMap<u8, Sender<Packet>>,
-                     receiver: Receiver<Packet>| {
-                        Box::new(ClientVideo::new(
-                            client.id,
-                            command_send,
-                            command_recv,
-                            receiver,
-                            senders,
-                        )) as Box<dyn ClientT>
-                    },
-                ) as BoxClient,
-            ),
-        ];
+        selected_drones: Option<&[DroneType]>,
+        selected_clients: Option<&[ClientType]>,
+    ) -> (
+        Vec<(GenericDrone, DroneRespawner)>,
+        Vec<GenericClient>,
+        Vec<Server>,
+    ) {
+        let drone_factories = self.drone_factories();
+        let client_factories = client_factories();
 
         // Filter factories based on the selected drones
-        let filtered_drones = Self::filter_nodes(selected_drones, drone_factories);
+        let filtered_drones = Arc::new(Self::filter_nodes(selected_drones, drone_factories));
         let filtered_clients = Self::filter_nodes(selected_clients, client_factories);
 
+        let (drone_assignment, client_assignment) =
+            self.assign(filtered_drones.len(), filtered_clients.len());
+        let mut link_senders = HashMap::new();
+
         let initialized_drones = Self::initialize_entities(
             &self.parser.drones,
             &self.channel_map,
             &self.drone_command_map,
             &self.node_event,
-            &filtered_drones,
+            &self.link_layer,
+            &mut link_senders,
+            filtered_drones.as_slice(),
+            &drone_assignment,
         );
 
         let initialized_clients = Self::initialize_entities(
@@ -278,7 +550,10 @@ impl NetworkInitializer {
             &self.channel_map,
             &self.drone_command_map,
             &self.node_event,
+            &self.link_layer,
+            &mut link_senders,
             &filtered_clients,
+            &client_assignment,
         );
 
         let initialized_servers = Self::initialize_entities(
@@ -286,88 +561,219 @@ impl NetworkInitializer {
             &self.channel_map,
             &self.drone_command_map,
             &self.node_event,
-            &[
-                |server: &ParsedServer, command_send, command_recv, senders, receiver| {
+            &self.link_layer,
+            &mut link_senders,
+            &[(
+                (),
+                |server: &ParsedServer,
+                 command_send: Sender<DroneEvent>,
+                 command_recv: Receiver<DroneCommand>,
+                 senders: HashMap<NodeId, Sender<Packet>>,
+                 receiver: Receiver<Packet>| {
                     Server::new(server.id, command_send, command_recv, receiver, senders)
                 },
-            ],
+            )],
+            &vec![0; self.parser.servers.len()],
         );
 
+        let drone_types: Vec<DroneType> = initialized_drones
+            .iter()
+            .map(|(drone_type, _)| **drone_type)
+            .collect();
+        let client_types: Vec<&ClientType> = initialized_clients
+            .iter()
+            .map(|(client_type, _)| *client_type)
+            .collect();
+        let manifest = self.build_manifest(&drone_types, &client_types);
+
+        self.node_channels
+            .write()
+            .expect("Channels lock poisoned")
+            .link_senders
+            .extend(link_senders);
+
+        // Keep what is needed to restart the drones before the channels are dropped
+        let respawners =
+            self.parser
+                .drones
+                .iter()
+                .enumerate()
+                .map(|(index, drone)| DroneRespawner {
+                    factories: Arc::clone(&filtered_drones),
+                    factory_index: drone_assignment[index],
+                    id: drone.id,
+                    capacity: self.parser.channel_capacity(drone.id),
+                    link_layer: self.link_layer.clone(),
+                    controller_send: self.node_event.sender.clone(),
+                    command_recv: self.drone_command_map[&drone.id].receiver.clone(),
+                    topology: Arc::clone(&self.topology),
+                    channels: Arc::clone(&self.node_channels),
+                });
+
+        self.manifest.set(manifest);
+        let initialized_drones: Vec<_> = initialized_drones
+            .into_iter()
+            .map(|(_, d)| d)
+            .zip(respawners)
+            .collect();
         self.channel_map.clear();
-        (initialized_drones, initialized_clients, initialized_servers)
+        (
+            initialized_drones,
+            initialized_clients.into_iter().map(|(_, c)| c).collect(),
+            initialized_servers.into_iter().map(|(_, s)| s).collect(),
+        )
+    }
+
+    fn log_level(&self, id: NodeId) -> LogLevel {
+        self.log_levels
+            .get(&id)
+            .copied()
+            .unwrap_or_else(|| self.parser.log_level(id))
+    }
+
+    /// Report the single points of failure of the topology according to the lint policy
+    fn check_lints(&self) -> Result<(), String> {
+        let warnings = lint_topology(&self.parser);
+        match self.lint_policy {
+            LintPolicy::Warn => {
+                for warning in &warnings {
+                    log_warning_as("lint", warning);
+                }
+                Ok(())
+            }
+            LintPolicy::Deny if warnings.is_empty() => Ok(()),
+            LintPolicy::Deny => {
+                let warnings: Vec<String> = warnings.iter().map(ToString::to_string).collect();
+                Err(format!("Topology warnings:\n{}", warnings.join("\n")))
+            }
+        }
+    }
+
+    /// Check the folders of the clients and servers, malformed assets would otherwise only be noticed
+    /// by the node threads. The folders are only read, the ids are assigned by `assign_content_ids`
+    fn prepare_assets(&self, manifest: &NodeManifest) -> Result<(), String> {
+        let mut asset_errors = check_manifest_assets(manifest);
+        let duplicates = find_duplicate_titles(manifest)
+            .into_iter()
+            .chain(find_duplicate_ids(manifest));
+        for duplicate in duplicates {
+            match self.duplicate_title_policy {
+                DuplicateTitlePolicy::Warn => {
+                    log_warning_as("assets", duplicate);
+                }
+                DuplicateTitlePolicy::Fail => asset_errors.push(duplicate),
+            }
+        }
+
+        if asset_errors.is_empty() {
+            Ok(())
+        } else {
+            let errors: Vec<String> = asset_errors.iter().map(ToString::to_string).collect();
+            Err(format!("Invalid assets:\n{}", errors.join("\n")))
+        }
     }
 
     /// Run the simulation
     /// ### Arguments
     /// - `selected_drones`: Vector of `DroneType`. If `None` uses all drones.
     /// ### Errors
-    /// Returns an error if the state is not initialized (`get_channels()`, `get_controller_recv()`, `get_controller_senders()` must be called first)
+    /// Returns an error if the state is not initialized (`get_channels()`, `get_controller_recv()`, `get_controller_senders()` must be called first),
+    /// the initialization folder of a client or a server is invalid or the lint policy denies a topology warning
     /// ### Panics
-    /// Panics if the tokio runtime fails to start
+    /// Panics if the tokio runtime or a node thread fails to start
     pub fn run_simulation(
         &mut self,
         selected_drones: Option<Vec<DroneType>>,
         selected_clients: Option<Vec<ClientType>>,
     ) -> Result<(), String> {
-        let res: Result<(), String> = match self.state {
-            State::Initialized => {
-                self.state = State::Running;
-                Ok(())
-            }
-            _ => Err("run_simulation() can only be called when initialized".into()),
-        };
-        res.as_ref()?;
+        if !matches!(self.state, State::Initialized) {
+            return Err("run_simulation() can only be called when initialized".into());
+        }
+
+        self.check_lints()?;
+        // The assets are checked on the planned manifest, before any node is instantiated
+        let selected_drones = selected_drones.as_deref();
+        let selected_clients = selected_clients.as_deref();
+        self.prepare_assets(&self.plan(selected_drones, selected_clients))?;
 
+        // A failed check leaves the initializer ready for another attempt
+        self.state = State::Running;
         let (drones, clients, servers) = self.initialize_network(selected_drones, selected_clients);
+        let manifest = self
+            .manifest
+            .get()
+            .expect("Manifest is set by initialize_network");
+        for line in manifest.to_string().lines() {
+            log_as("manifest", line);
+        }
+
         let mut node_handlers: HashMap<NodeId, JoinHandle<()>> = HashMap::new();
 
-        for (i, mut drone) in drones.into_iter().enumerate() {
+        for (entry, (drone, respawner)) in manifest.of_kind(NodeType::Drone).zip(drones) {
+            let id = entry.id; // Needed because drones do not implement get_id method
+            let events = self.simulation_event.sender.clone();
+            let policy = self
+                .restart_policies
+                .get(&id)
+                .copied()
+                .unwrap_or(self.default_restart_policy);
+
             node_handlers.insert(
-                self.parser.drones[i].id, // Needed because drones do not implement get_id method
-                thread::spawn(move || {
-                    drone.run();
-                }),
+                id,
+                thread::Builder::new()
+                    .name(entry.thread_name())
+                    .spawn(move || {
+                        supervise_drone(id, drone, &events, policy, &respawner);
+                    })
+                    .expect("Failed to spawn the node thread"),
             );
         }
 
-        for (i, client) in clients.into_iter().enumerate() {
-            let client_id = client.get_id();
+        for (entry, client) in manifest.of_kind(NodeType::Client).zip(clients) {
+            let init_file_path = entry
+                .asset_path
+                .clone()
+                .expect("Clients have an asset path");
 
-            // Determine the path based on the client type
-            let init_file_path = if client
-                .as_ref()
-                .as_any()
-                .downcast_ref::<ClientVideo>()
-                .is_some()
-            {
-                let client_number = (i % CLIENT_VIDEO_CONFIGURATIONS_NUM) + 1; // Cycle through 1 to 5
-                format!("./initialization_files/client_video/client{client_number}")
-            } else {
-                let client_number = (i % CLIENT_AUDIO_CONFIGURATIONS_NUM) + 1; // Cycle through 1 to 5
-                format!("./initialization_files/client_audio/client{client_number}")
-            };
+            let id = entry.id;
+            let events = self.simulation_event.sender.clone();
+            let log_level = self.log_level(id);
 
             node_handlers.insert(
-                client_id,
-                thread::spawn(move || {
-                    client.with_info();
-                    client.with_error();
-                    client.run(&init_file_path);
-                }),
+                id,
+                thread::Builder::new()
+                    .name(entry.thread_name())
+                    .spawn(move || {
+                        apply_log_level!(client, log_level);
+                        supervise_node(id, &events, || {
+                            client.run(&init_file_path);
+                        });
+                    })
+                    .expect("Failed to spawn the node thread"),
             );
         }
 
-        for (i, mut server) in servers.into_iter().enumerate() {
-            let server_number = (i % SERVER_CONFIGURATIONS_NUM) + 1; // Cycles through 1 to 5
-            let init_file_path = format!("./initialization_files/server/server{server_number}");
+        for (entry, mut server) in manifest.of_kind(NodeType::Server).zip(servers) {
+            let init_file_path = entry
+                .asset_path
+                .clone()
+                .expect("Servers have an asset path");
+
+            let id = entry.id;
+            let events = self.simulation_event.sender.clone();
+            let log_level = self.log_level(id);
 
             node_handlers.insert(
-                server.get_id(),
-                thread::spawn(move || {
-                    server.with_info();
-                    server.with_error();
-                    server.run(&init_file_path);
-                }),
+                id,
+                thread::Builder::new()
+                    .name(entry.thread_name())
+                    .spawn(move || {
+                        apply_log_level!(server, log_level);
+                        supervise_node(id, &events, || {
+                            server.run(&init_file_path);
+                        });
+                    })
+                    .expect("Failed to spawn the node thread"),
             );
         }
 
@@ -376,19 +782,20 @@ impl NetworkInitializer {
             // Set up Ctrl+C handler
             let _command_senders = self.get_controller_senders();
             ctrlc::set_handler(move || {
-                println!("Received Ctrl+C, shutting down...");
+                log_as("simulation", "Received Ctrl+C, shutting down...");
                 std::process::exit(0);
             })
             .expect("Error setting Ctrl+C handler");
         }
 
-        for (id, handler) in node_handlers.drain() {
+        for handler in node_handlers.into_values() {
+            let name = handler.thread().name().unwrap_or("unnamed").to_string();
             match handler.join() {
                 Ok(()) => {
-                    println!("Node {id} shut down successfully");
+                    log_as(&name, "shut down successfully");
                 }
                 Err(err) => {
-                    eprintln!("Thread for node {id} panicked: {err:?}");
+                    log_error_as(&name, format!("thread panicked: {err:?}"));
                 }
             }
         }
@@ -12,12 +12,14 @@ use crate::parsed_nodes::{ParsedClient, ParsedDrone};
 
 pub(crate) type BoxDrone = Box<
     dyn Fn(
-        &ParsedDrone,
-        Sender<DroneEvent>,
-        Receiver<DroneCommand>,
-        HashMap<u8, Sender<Packet>>,
-        Receiver<Packet>,
-    ) -> Box<dyn Drone>,
+            &ParsedDrone,
+            Sender<DroneEvent>,
+            Receiver<DroneCommand>,
+            HashMap<u8, Sender<Packet>>,
+            Receiver<Packet>,
+        ) -> Box<dyn Drone>
+        + Send
+        + Sync,
 >;
 
 pub(crate) type BoxClient = Box<
@@ -53,3 +55,42 @@ macro_rules! create_drone_factories {
         ]
     };
 }
+
+// Macro that enables on a client or a server every log level up to `$level`
+#[macro_export]
+macro_rules! apply_log_level {
+    ($node:expr, $level:expr) => {{
+        if $level >= $crate::log_level::LogLevel::Error {
+            $node.with_error();
+        }
+        if $level >= $crate::log_level::LogLevel::Info {
+            $node.with_info();
+        }
+        if $level >= $crate::log_level::LogLevel::Warning {
+            $node.with_warning();
+        }
+        if $level >= $crate::log_level::LogLevel::Debug {
+            $node.with_debug();
+        }
+    }};
+}
+
+/// Factory of a drone implementation registered with `NetworkInitializer::register_drone()`
+pub(crate) fn custom_drone_factory<D: Drone + 'static>() -> BoxDrone {
+    Box::new(
+        |parsed_drone: &ParsedDrone,
+         command_send: Sender<DroneEvent>,
+         command_recv: Receiver<DroneCommand>,
+         senders: HashMap<u8, Sender<Packet>>,
+         receiver: Receiver<Packet>| {
+            Box::new(D::new(
+                parsed_drone.id,
+                command_send,
+                command_recv,
+                receiver,
+                senders,
+                parsed_drone.pdr,
+            )) as Box<dyn Drone>
+        },
+    )
+}
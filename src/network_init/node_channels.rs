@@ -0,0 +1,14 @@
+use std::collections::HashMap;
+
+use crossbeam::channel::Sender;
+use wg_internal::{controller::DroneCommand, network::NodeId, packet::Packet};
+
+/// Senders of the running network, shared by the controller and the supervisors of the drones
+/// so that both see the channels of restarted and added drones
+#[derive(Debug, Default)]
+pub(crate) struct NodeChannels {
+    pub(crate) packet_senders: HashMap<NodeId, Sender<Packet>>,
+    pub(crate) command_senders: HashMap<NodeId, Sender<DroneCommand>>,
+    /// Sender given to the first node to reach the second one, it goes through the link layer
+    pub(crate) link_senders: HashMap<(NodeId, NodeId), Sender<Packet>>,
+}
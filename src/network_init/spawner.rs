@@ -0,0 +1,106 @@
+use std::fmt::{self, Debug};
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+use crossbeam::channel::Sender;
+use wg_internal::{
+    controller::{DroneCommand, DroneEvent},
+    packet::Packet,
+};
+
+use crate::{
+    channel::Channel, parsed_nodes::ParsedDrone, simulation_event::SimulationEvent,
+    utils::parser::Parser,
+};
+
+use super::{
+    links::LinkLayer, net_utils::BoxDrone, node_channels::NodeChannels,
+    supervisor::supervise_drone, supervisor::DroneRespawner, DroneType, NetworkInitializer,
+    RestartPolicy,
+};
+
+/// Creates drones after the simulation has started, using every available implementation
+#[derive(Clone)]
+pub(crate) struct DroneSpawner {
+    factories: Arc<Vec<(DroneType, BoxDrone)>>,
+    link_layer: LinkLayer,
+    // capacity of the drones without their own
+    capacity: Option<usize>,
+    controller_send: Sender<DroneEvent>,
+    simulation_send: Sender<SimulationEvent>,
+    restart_policy: RestartPolicy,
+    topology: Arc<RwLock<Parser>>,
+    channels: Arc<RwLock<NodeChannels>>,
+}
+
+impl Debug for DroneSpawner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let drone_types: Vec<_> = self.factories.iter().map(|(kind, _)| kind).collect();
+        f.debug_struct("DroneSpawner")
+            .field("factories", &drone_types)
+            .field("capacity", &self.capacity)
+            .field("restart_policy", &self.restart_policy)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DroneSpawner {
+    /// Create a spawner using the implementations, channels and restart policy of `net_init`
+    pub(crate) fn new(net_init: &NetworkInitializer) -> Self {
+        DroneSpawner {
+            factories: Arc::new(net_init.drone_factories()),
+            link_layer: net_init.link_layer.clone(),
+            capacity: net_init.parser.channel_capacity,
+            controller_send: net_init.node_event.sender.clone(),
+            simulation_send: net_init.simulation_event.sender.clone(),
+            restart_policy: net_init.default_restart_policy,
+            topology: Arc::clone(&net_init.topology),
+            channels: Arc::clone(&net_init.node_channels),
+        }
+    }
+
+    /// Create the drone, connect it to its neighbours and run it in a new supervised thread.
+    /// Its senders are added to the shared channels, so that the controller can reach it.
+    /// Returns `None` if `drone_type` is not available or the thread cannot be started
+    pub(crate) fn spawn(&self, drone: &ParsedDrone, drone_type: DroneType) -> Option<()> {
+        let factory_index = self
+            .factories
+            .iter()
+            .position(|(kind, _)| *kind == drone_type)?;
+
+        let id = drone.id;
+        let capacity = drone.channel_capacity.or(self.capacity);
+        let packet_channel: Channel<Packet> = Channel::with_capacity(capacity);
+        let command_channel: Channel<DroneCommand> = Channel::default();
+
+        let respawner = DroneRespawner {
+            factories: Arc::clone(&self.factories),
+            factory_index,
+            id,
+            capacity,
+            link_layer: self.link_layer.clone(),
+            controller_send: self.controller_send.clone(),
+            command_recv: command_channel.receiver,
+            topology: Arc::clone(&self.topology),
+            channels: Arc::clone(&self.channels),
+        };
+        self.channels
+            .write()
+            .expect("Channels lock poisoned")
+            .command_senders
+            .insert(id, command_channel.sender);
+        // A new drone is in no group of the partition, none of its links is cut
+        let instance = respawner.instantiate(drone, &[], packet_channel);
+
+        let events = self.simulation_send.clone();
+        let policy = self.restart_policy;
+        thread::Builder::new()
+            .name(format!("drone-{id}-{}", drone_type.name()))
+            .spawn(move || {
+                supervise_drone(id, instance, &events, policy, &respawner);
+            })
+            .ok()?;
+
+        Some(())
+    }
+}
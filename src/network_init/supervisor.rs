@@ -0,0 +1,194 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, RwLock};
+
+use crossbeam::channel::{Receiver, Sender};
+use wg_internal::{
+    controller::{DroneCommand, DroneEvent},
+    network::NodeId,
+    packet::Packet,
+};
+
+use crate::{
+    channel::Channel, log_sink::log, parsed_nodes::ParsedDrone, simulation_event::SimulationEvent,
+    utils::parser::Parser,
+};
+
+use super::{
+    links::LinkLayer, net_utils::BoxDrone, node_channels::NodeChannels, DroneType, GenericDrone,
+};
+
+/// What to do when the thread of a drone panics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RestartPolicy {
+    /// Report the panic and let the node die
+    #[default]
+    Never,
+    /// Report the panic and start a new instance of the drone, at most `max_restarts` times
+    OnPanic { max_restarts: u32 },
+}
+
+impl RestartPolicy {
+    fn allows_restart(self, restarts_done: u32) -> bool {
+        match self {
+            RestartPolicy::Never => false,
+            RestartPolicy::OnPanic { max_restarts } => restarts_done < max_restarts,
+        }
+    }
+}
+
+/// Everything needed to create a new instance of a drone that panicked
+pub(crate) struct DroneRespawner {
+    pub(crate) factories: Arc<Vec<(DroneType, BoxDrone)>>,
+    pub(crate) factory_index: usize,
+    pub(crate) id: NodeId,
+    pub(crate) capacity: Option<usize>,
+    pub(crate) link_layer: LinkLayer,
+    pub(crate) controller_send: Sender<DroneEvent>,
+    pub(crate) command_recv: Receiver<DroneCommand>,
+    // current topology and channels of the network, shared with the controller
+    pub(crate) topology: Arc<RwLock<Parser>>,
+    pub(crate) channels: Arc<RwLock<NodeChannels>>,
+}
+
+impl DroneRespawner {
+    /// Create the drone again with a fresh packet channel, connected to its neighbours in the current topology.
+    /// The command channel is kept so that the controller senders remain valid.
+    /// Returns `None` if the drone is no longer part of the topology
+    fn respawn(&self) -> Option<GenericDrone> {
+        let (drone, cut) = {
+            let topology = self.topology.read().expect("Topology lock poisoned");
+            let drone = topology.drones.iter().find(|d| d.id == self.id)?.clone();
+            let cut: Vec<NodeId> = drone
+                .connected_drone_ids
+                .iter()
+                .copied()
+                .filter(|neighbour_id| topology.is_cut(drone.id, *neighbour_id))
+                .collect();
+            (drone, cut)
+        };
+
+        Some(self.instantiate(&drone, &cut, Channel::with_capacity(self.capacity)))
+    }
+
+    /// Create `drone` receiving its packets from `channel` and connect it to its neighbours in both directions.
+    /// The neighbours get a new link to `channel`, while the links leaving the drone are reused when they exist.
+    /// The links to the `cut` neighbours are only recorded, they are given to the nodes when the partition is healed
+    pub(crate) fn instantiate(
+        &self,
+        drone: &ParsedDrone,
+        cut: &[NodeId],
+        channel: Channel<Packet>,
+    ) -> GenericDrone {
+        let mut neighbour_senders = HashMap::new();
+        {
+            let mut channels = self.channels.write().expect("Channels lock poisoned");
+            channels
+                .packet_senders
+                .insert(drone.id, channel.sender.clone());
+
+            for neighbour_id in &drone.connected_drone_ids {
+                let is_cut = cut.contains(neighbour_id);
+
+                if let Some(command_send) = channels.command_senders.get(neighbour_id).cloned() {
+                    let incoming =
+                        self.link_layer
+                            .connect(*neighbour_id, drone.id, &channel.sender);
+                    if !is_cut {
+                        let _ =
+                            command_send.send(DroneCommand::AddSender(drone.id, incoming.clone()));
+                    }
+                    channels
+                        .link_senders
+                        .insert((*neighbour_id, drone.id), incoming);
+                }
+
+                let outgoing = match channels.link_senders.get(&(drone.id, *neighbour_id)) {
+                    Some(sender) => Some(sender.clone()),
+                    None => channels
+                        .packet_senders
+                        .get(neighbour_id)
+                        .map(|sender| self.link_layer.connect(drone.id, *neighbour_id, sender)),
+                };
+                if let Some(outgoing) = outgoing {
+                    channels
+                        .link_senders
+                        .insert((drone.id, *neighbour_id), outgoing.clone());
+                    if !is_cut {
+                        neighbour_senders.insert(*neighbour_id, outgoing);
+                    }
+                }
+            }
+        }
+
+        let (_, factory) = &self.factories[self.factory_index];
+        factory(
+            drone,
+            self.controller_send.clone(),
+            self.command_recv.clone(),
+            neighbour_senders,
+            channel.receiver,
+        )
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Run `run` catching its panic, which is reported on `events` as soon as it happens
+pub(crate) fn run_supervised<F: FnOnce()>(
+    id: NodeId,
+    events: &Sender<SimulationEvent>,
+    run: F,
+) -> Result<(), Box<dyn Any + Send>> {
+    panic::catch_unwind(AssertUnwindSafe(run)).inspect_err(|payload| {
+        let _ = events.send(SimulationEvent::NodePanicked {
+            id,
+            message: panic_message(&**payload),
+        });
+    })
+}
+
+/// Run a client or a server, the panic is reported and then propagated to the thread
+pub(crate) fn supervise_node<F: FnOnce()>(id: NodeId, events: &Sender<SimulationEvent>, run: F) {
+    if let Err(payload) = run_supervised(id, events, run) {
+        panic::resume_unwind(payload);
+    }
+}
+
+/// Run a drone restarting it according to `policy`.
+/// When no more restarts are allowed the last panic is propagated to the thread
+pub(crate) fn supervise_drone(
+    id: NodeId,
+    mut drone: GenericDrone,
+    events: &Sender<SimulationEvent>,
+    policy: RestartPolicy,
+    respawner: &DroneRespawner,
+) {
+    let mut restarts = 0;
+
+    while let Err(payload) = run_supervised(id, events, || drone.run()) {
+        if !policy.allows_restart(restarts) {
+            panic::resume_unwind(payload);
+        }
+
+        // A drone removed from the topology (e.g. by `crash_node`) is not brought back
+        let Some(new_drone) = respawner.respawn() else {
+            panic::resume_unwind(payload);
+        };
+        restarts += 1;
+        drone = new_drone;
+        log(format!(
+            "restarted after a panic, {restarts} restarts so far"
+        ));
+        let _ = events.send(SimulationEvent::NodeRestarted { id, restarts });
+    }
+}
@@ -0,0 +1,254 @@
+use std::ops::RangeInclusive;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use wg_internal::network::NodeId;
+
+use crate::{
+    errors::ChaosError, log_sink::log_as, simulation_event::SimulationEvent, utils::parser::Parser,
+};
+
+use super::scenario::{ScenarioAction, ScenarioStep};
+use super::SimulationController;
+
+/// Rate and kind of the changes applied by the chaos engine
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChaosConfig {
+    /// Seed of the engine, the seed of the simulation is used if not set
+    pub seed: Option<u64>,
+    /// Time between two actions
+    pub interval: Duration,
+    /// Number of actions to take before stopping
+    pub actions: usize,
+    /// Relative probability of crashing a drone
+    pub crash_weight: u32,
+    /// Relative probability of changing the PDR of a drone
+    pub pdr_weight: u32,
+    /// Relative probability of removing a link or restoring a removed one
+    pub link_weight: u32,
+    /// PDRs are drawn from this range
+    pub pdr_range: RangeInclusive<f32>,
+    /// Servers never go below this number of connections
+    pub min_server_degree: usize,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        ChaosConfig {
+            seed: None,
+            interval: Duration::from_secs(1),
+            actions: 10,
+            crash_weight: 1,
+            pdr_weight: 2,
+            link_weight: 2,
+            pdr_range: 0.0..=0.5,
+            min_server_degree: 2,
+        }
+    }
+}
+
+impl ChaosConfig {
+    fn check(&self) -> Result<(), ChaosError> {
+        let (min_pdr, max_pdr) = (*self.pdr_range.start(), *self.pdr_range.end());
+        // Also rejects NaN
+        if !((0.0..=1.0).contains(&min_pdr) && (min_pdr..=1.0).contains(&max_pdr)) {
+            return Err(ChaosError::InvalidPdrRange(min_pdr, max_pdr));
+        }
+        if self.min_server_degree < 2 {
+            return Err(ChaosError::InvalidServerDegree(self.min_server_degree));
+        }
+        // The last action must be schedulable
+        let last_action = u32::try_from(self.actions)
+            .ok()
+            .and_then(|actions| self.interval.checked_mul(actions));
+        if self.actions == 0 || last_action.is_none() {
+            return Err(ChaosError::InvalidActions(self.actions));
+        }
+        let total = [self.crash_weight, self.pdr_weight, self.link_weight]
+            .into_iter()
+            .try_fold(0u32, u32::checked_add);
+        if matches!(total, None | Some(0)) {
+            return Err(ChaosError::InvalidWeights);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ChaosKind {
+    Crash,
+    Pdr,
+    Link,
+}
+
+/// Randomly crashes drones, changes PDRs and toggles links of a running simulation.
+/// Every change goes through the controller, so clients always reach every server,
+/// and is reported as `SimulationEvent::ChaosApplied`
+#[derive(Debug)]
+pub struct ChaosEngine {
+    controller: SimulationController,
+    config: ChaosConfig,
+    rng: StdRng,
+    // links removed by the engine, candidates to be restored
+    removed_links: Vec<(NodeId, NodeId)>,
+}
+
+impl ChaosEngine {
+    /// Create an engine changing the network of `controller`
+    /// # Errors
+    /// Returns an error if the PDR range is not within `[0, 1]`, the minimum server degree is below 2,
+    /// there are no actions or they cannot be scheduled, or the weights are all zero or overflow
+    pub fn new(controller: &SimulationController, config: ChaosConfig) -> Result<Self, ChaosError> {
+        config.check()?;
+        let seed = config.seed.unwrap_or_else(|| controller.seed());
+
+        Ok(ChaosEngine {
+            controller: controller.clone(),
+            config,
+            rng: StdRng::seed_from_u64(seed),
+            removed_links: Vec::new(),
+        })
+    }
+
+    /// Take the configured number of actions, blocking until the last one.
+    /// Returns the actions that were applied
+    pub fn run(mut self) -> Vec<ScenarioStep> {
+        let start = Instant::now();
+        let mut applied = Vec::new();
+
+        for i in 1..=self.config.actions {
+            let due = start + self.config.interval * u32::try_from(i).unwrap_or(u32::MAX);
+            thread::sleep(due.saturating_duration_since(Instant::now()));
+
+            let Some(action) = self.next_action() else {
+                continue;
+            };
+            let step = ScenarioStep {
+                at_ms: u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX),
+                action,
+            };
+            log_as("chaos", format_args!("t={}ms {}", step.at_ms, step.action));
+            self.controller.report(SimulationEvent::ChaosApplied(step));
+            applied.push(step);
+        }
+
+        applied
+    }
+
+    /// Run the engine in a dedicated thread
+    #[must_use]
+    pub fn spawn(self) -> JoinHandle<Vec<ScenarioStep>> {
+        thread::spawn(move || self.run())
+    }
+
+    /// Draw and apply an action, falling back to the other kinds when none of the drawn kind is allowed
+    fn next_action(&mut self) -> Option<ScenarioAction> {
+        let mut kinds = vec![
+            (ChaosKind::Crash, self.config.crash_weight),
+            (ChaosKind::Pdr, self.config.pdr_weight),
+            (ChaosKind::Link, self.config.link_weight),
+        ];
+        kinds.retain(|(_, weight)| *weight > 0);
+
+        while !kinds.is_empty() {
+            let total: u32 = kinds.iter().map(|(_, weight)| weight).sum();
+            let mut draw = self.rng.gen_range(0..total);
+            let index = kinds
+                .iter()
+                .position(|(_, weight)| {
+                    let found = draw < *weight;
+                    draw = draw.saturating_sub(*weight);
+                    found
+                })
+                .unwrap_or(0);
+
+            let (kind, _) = kinds.remove(index);
+            let action = match kind {
+                ChaosKind::Crash => self.crash(),
+                ChaosKind::Pdr => self.change_pdr(),
+                ChaosKind::Link => self.toggle_link(),
+            };
+            if action.is_some() {
+                return action;
+            }
+        }
+
+        None
+    }
+
+    /// Whether removing the connections of `ids` to `removed` keeps every server above the minimum degree,
+    /// the links cut by a partition do not count
+    fn keeps_server_degree(&self, topology: &Parser, ids: &[NodeId], removed: NodeId) -> bool {
+        topology
+            .servers
+            .iter()
+            .filter(|server| ids.contains(&server.id))
+            .all(|server| {
+                let degree = server
+                    .connected_drone_ids
+                    .iter()
+                    .filter(|id| **id != removed && !topology.is_cut(server.id, **id))
+                    .count();
+                degree >= self.config.min_server_degree
+            })
+    }
+
+    fn crash(&mut self) -> Option<ScenarioAction> {
+        let topology = self.controller.topology();
+        let mut candidates: Vec<_> = topology
+            .drones
+            .iter()
+            .filter(|d| self.keeps_server_degree(&topology, &d.connected_drone_ids, d.id))
+            .map(|d| d.id)
+            .collect();
+        candidates.shuffle(&mut self.rng);
+
+        let node = candidates
+            .into_iter()
+            .find(|id| self.controller.crash_node(*id).is_ok())?;
+        self.removed_links.retain(|(a, b)| *a != node && *b != node);
+        Some(ScenarioAction::Crash { node })
+    }
+
+    fn change_pdr(&mut self) -> Option<ScenarioAction> {
+        let topology = self.controller.topology();
+        let node = topology.drones.choose(&mut self.rng)?.id;
+        let pdr = self.rng.gen_range(self.config.pdr_range.clone());
+
+        self.controller.set_packet_drop_rate(node, pdr).ok()?;
+        Some(ScenarioAction::SetPdr { node, pdr })
+    }
+
+    /// Restore a removed link or remove an existing one, with the same probability
+    fn toggle_link(&mut self) -> Option<ScenarioAction> {
+        if !self.removed_links.is_empty() && self.rng.gen_bool(0.5) {
+            let index = self.rng.gen_range(0..self.removed_links.len());
+            let (a, b) = self.removed_links.swap_remove(index);
+            if self.controller.add_link(a, b).is_ok() {
+                return Some(ScenarioAction::AddLink { a, b });
+            }
+        }
+
+        let topology = self.controller.topology();
+        let mut candidates: Vec<(NodeId, NodeId)> = topology
+            .drones
+            .iter()
+            .flat_map(|d| d.connected_drone_ids.iter().map(move |n| (d.id, *n)))
+            // Each link once, links to clients and servers start from the drone
+            .filter(|(a, b)| a < b || topology.drones.iter().all(|d| d.id != *b))
+            // Links cut by a partition are given back by heal()
+            .filter(|(a, b)| !topology.is_cut(*a, *b))
+            .filter(|(a, b)| self.keeps_server_degree(&topology, &[*b], *a))
+            .collect();
+        candidates.shuffle(&mut self.rng);
+
+        let (a, b) = candidates
+            .into_iter()
+            .find(|(a, b)| self.controller.remove_link(*a, *b).is_ok())?;
+        self.removed_links.push((a, b));
+        Some(ScenarioAction::RemoveLink { a, b })
+    }
+}
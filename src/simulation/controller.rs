@@ -0,0 +1,575 @@
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use std::thread::{self, JoinHandle};
+
+use crossbeam::channel::{select, Receiver, Sender};
+use wg_internal::{
+    controller::{DroneCommand, DroneEvent},
+    network::NodeId,
+    packet::Packet,
+};
+
+use crate::{
+    channel::Channel,
+    errors::ControllerError,
+    log_sink::log_error_as,
+    manifest::{ManifestHandle, NodeManifestEntry},
+    network_init::{links::LinkLayer, node_channels::NodeChannels, spawner::DroneSpawner},
+    parsed_nodes::{NodeType, ParsedDrone},
+    simulation_event::{ControllerEvent, SimulationEvent},
+    utils::parser::Parser,
+    DroneType, NetworkInitializer,
+};
+
+/// Simulation controller owning the controller side of the network channels.
+/// It delivers the `ControllerShortcut` packets and forwards every event of the nodes
+/// and of the initializer (e.g. node panics) to `events()`.
+/// Clones share the same channels and topology, so it can be used while its event loop
+/// and `NetworkInitializer::run_simulation()` are running.
+#[derive(Debug, Clone)]
+pub struct SimulationController {
+    channels: Arc<RwLock<NodeChannels>>,
+    // topology of the running network, kept valid by the topology changes
+    topology: Arc<RwLock<Parser>>,
+    manifest: ManifestHandle,
+    node_event: Receiver<DroneEvent>,
+    simulation_recv: Receiver<SimulationEvent>,
+    forwarded_event: Channel<ControllerEvent>,
+    link_layer: LinkLayer,
+    spawner: DroneSpawner,
+    simulation_event: Sender<SimulationEvent>,
+    seed: u64,
+}
+
+impl SimulationController {
+    /// Take the controller channels from the network initializer,
+    /// which is then ready for `run_simulation()`
+    pub fn new(net_init: &mut NetworkInitializer) -> Self {
+        // The senders are shared with the initializer, these calls only make it ready to run
+        let node_event = net_init.get_controller_recv();
+        let _ = net_init.get_controller_senders();
+        let _ = net_init.get_channels();
+
+        SimulationController {
+            channels: net_init.get_node_channels(),
+            topology: net_init.get_topology_handle(),
+            manifest: net_init.get_manifest_handle(),
+            node_event,
+            simulation_recv: net_init.get_simulation_recv(),
+            forwarded_event: Channel::default(),
+            link_layer: net_init.get_link_layer(),
+            spawner: net_init.get_drone_spawner(),
+            simulation_event: net_init.get_simulation_sender(),
+            seed: net_init.get_seed(),
+        }
+    }
+
+    /// Get the events sent by the nodes, after the shortcuts have been delivered,
+    /// together with the events of the initializer such as node panics
+    #[must_use]
+    pub fn events(&self) -> Receiver<ControllerEvent> {
+        self.forwarded_event.receiver.clone()
+    }
+
+    /// Handle the events until all the nodes are disconnected
+    pub fn run(&self) {
+        loop {
+            select! {
+                recv(self.node_event) -> event => {
+                    let Ok(event) = event else {
+                        break;
+                    };
+                    if let DroneEvent::ControllerShortcut(packet) = &event {
+                        self.deliver_shortcut(packet.clone());
+                    }
+                    let _ = self.forwarded_event.sender.send(ControllerEvent::Node(event));
+                }
+                recv(self.simulation_recv) -> event => {
+                    if let Ok(event) = event {
+                        let _ = self
+                            .forwarded_event
+                            .sender
+                            .send(ControllerEvent::Simulation(event));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Run the event loop in a dedicated thread
+    #[must_use]
+    pub fn spawn(&self) -> JoinHandle<()> {
+        let controller = self.clone();
+        thread::spawn(move || controller.run())
+    }
+
+    /// Send the packet directly to its destination, the last hop of its route
+    fn deliver_shortcut(&self, packet: Packet) {
+        let Some(destination) = packet.routing_header.hops.last().copied() else {
+            log_error_as(
+                "controller",
+                format!("shortcut packet without destination: {packet:?}"),
+            );
+            return;
+        };
+
+        // The lock is released before sending, a full channel must not block the topology changes
+        let sender = self
+            .channels
+            .read()
+            .expect("Controller lock poisoned")
+            .packet_senders
+            .get(&destination)
+            .cloned();
+        match sender {
+            Some(sender) => {
+                if sender.send(packet).is_err() {
+                    log_error_as(
+                        "controller",
+                        format!("shortcut destination {destination} is not reachable"),
+                    );
+                }
+            }
+            None => log_error_as(
+                "controller",
+                format!("shortcut destination {destination} not found"),
+            ),
+        }
+    }
+
+    /// Returns a copy of the current topology
+    #[must_use]
+    pub fn topology(&self) -> Parser {
+        self.topology
+            .read()
+            .expect("Topology lock poisoned")
+            .clone()
+    }
+
+    /// Returns the seed of the simulation
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Report an event of the initializer, it is forwarded to `events()`
+    pub(crate) fn report(&self, event: SimulationEvent) {
+        let _ = self.simulation_event.send(event);
+    }
+
+    fn send_command(&self, id: NodeId, command: DroneCommand) -> Result<(), ControllerError> {
+        let channels = self.channels.read().expect("Controller lock poisoned");
+        channels
+            .command_senders
+            .get(&id)
+            .ok_or(ControllerError::NodeNotFound(id))?
+            .send(command)
+            .map_err(|_| ControllerError::NodeUnreachable(id))
+    }
+
+    /// Set the packet drop rate of a drone
+    /// # Errors
+    /// Returns an error if `pdr` is not in `[0, 1]`, the node is not a drone or it cannot be reached
+    pub fn set_packet_drop_rate(&self, id: NodeId, pdr: f32) -> Result<(), ControllerError> {
+        if !(0.0..=1.0).contains(&pdr) {
+            return Err(ControllerError::InvalidPdr(pdr));
+        }
+
+        let mut topology = self.topology.write().expect("Topology lock poisoned");
+        let Some(drone) = topology.drones.iter_mut().find(|d| d.id == id) else {
+            return Err(ControllerError::NotADrone(id));
+        };
+
+        self.send_command(id, DroneCommand::SetPacketDropRate(pdr))?;
+
+        drone.pdr = pdr;
+        self.manifest.update(|manifest| {
+            if let Some(entry) = manifest.get_mut(id) {
+                entry.pdr = Some(pdr);
+            }
+        });
+        Ok(())
+    }
+
+    /// Let `id` send packets to `neighbour_id`
+    /// # Errors
+    /// Returns an error if one of the nodes does not exist or `id` cannot be reached
+    pub fn add_sender(&self, id: NodeId, neighbour_id: NodeId) -> Result<(), ControllerError> {
+        let neighbour_sender = self.connect(id, neighbour_id)?;
+        self.restore_sender(id, neighbour_id, neighbour_sender)
+    }
+
+    /// Returns a new link from `id` to `neighbour_id`
+    fn connect(&self, id: NodeId, neighbour_id: NodeId) -> Result<Sender<Packet>, ControllerError> {
+        let channels = self.channels.read().expect("Controller lock poisoned");
+        let sender = channels
+            .packet_senders
+            .get(&neighbour_id)
+            .ok_or(ControllerError::NodeNotFound(neighbour_id))?;
+        Ok(self.link_layer.connect(id, neighbour_id, sender))
+    }
+
+    /// Let `id` send packets to `neighbour_id` through `sender`, e.g. a link it used before a partition
+    fn restore_sender(
+        &self,
+        id: NodeId,
+        neighbour_id: NodeId,
+        sender: Sender<Packet>,
+    ) -> Result<(), ControllerError> {
+        self.send_command(id, DroneCommand::AddSender(neighbour_id, sender.clone()))?;
+
+        self.channels
+            .write()
+            .expect("Controller lock poisoned")
+            .link_senders
+            .insert((id, neighbour_id), sender);
+        Ok(())
+    }
+
+    /// Returns the sender `id` uses to reach `neighbour_id`, held back if the link is cut
+    fn link_sender(&self, id: NodeId, neighbour_id: NodeId) -> Option<Sender<Packet>> {
+        self.channels
+            .read()
+            .expect("Controller lock poisoned")
+            .link_senders
+            .get(&(id, neighbour_id))
+            .cloned()
+    }
+
+    /// Stop `id` from sending packets to `neighbour_id`
+    /// # Errors
+    /// Returns an error if `id` does not exist or cannot be reached
+    pub fn remove_sender(&self, id: NodeId, neighbour_id: NodeId) -> Result<(), ControllerError> {
+        self.send_command(id, DroneCommand::RemoveSender(neighbour_id))?;
+
+        self.channels
+            .write()
+            .expect("Controller lock poisoned")
+            .link_senders
+            .remove(&(id, neighbour_id));
+        Ok(())
+    }
+
+    /// Make a drone crash
+    /// # Errors
+    /// Returns an error if the drone does not exist or cannot be reached
+    pub fn crash(&self, id: NodeId) -> Result<(), ControllerError> {
+        self.send_command(id, DroneCommand::Crash)
+    }
+
+    /// Connect two nodes, in both directions
+    /// # Errors
+    /// Returns an error if the nodes are already connected, the resulting topology is invalid
+    /// or one of the nodes cannot be reached
+    pub fn add_link(&self, a: NodeId, b: NodeId) -> Result<(), ControllerError> {
+        let mut topology = self.topology.write().expect("Topology lock poisoned");
+        let mut updated = topology.clone();
+
+        let a_connections = updated
+            .connected_ids_mut(a)
+            .ok_or(ControllerError::NodeNotFound(a))?;
+        if a_connections.contains(&b) {
+            return Err(ControllerError::AlreadyConnected(a, b));
+        }
+        a_connections.push(b);
+        updated
+            .connected_ids_mut(b)
+            .ok_or(ControllerError::NodeNotFound(b))?
+            .push(a);
+
+        updated.check_topology()?;
+        updated.check_reachability_from(&topology)?;
+
+        if updated.is_cut(a, b) {
+            // The link is held back like the other cut links, the nodes get it on heal
+            let a_sender = self.connect(a, b)?;
+            let b_sender = self.connect(b, a)?;
+            let mut channels = self.channels.write().expect("Controller lock poisoned");
+            channels.link_senders.insert((a, b), a_sender);
+            channels.link_senders.insert((b, a), b_sender);
+        } else {
+            self.add_sender(a, b)?;
+            if let Err(err) = self.add_sender(b, a) {
+                // Undo the first side, the nodes must match the topology
+                let _ = self.remove_sender(a, b);
+                return Err(err);
+            }
+        }
+
+        *topology = updated;
+        self.sync_manifest(&topology, &[a, b]);
+        Ok(())
+    }
+
+    /// Disconnect two nodes, in both directions
+    /// # Errors
+    /// Returns an error if the nodes are not connected, the resulting topology is invalid
+    /// (e.g. a client can no longer reach a server) or one of the nodes cannot be reached
+    pub fn remove_link(&self, a: NodeId, b: NodeId) -> Result<(), ControllerError> {
+        let mut topology = self.topology.write().expect("Topology lock poisoned");
+        let mut updated = topology.clone();
+
+        let a_connections = updated
+            .connected_ids_mut(a)
+            .ok_or(ControllerError::NodeNotFound(a))?;
+        if !a_connections.contains(&b) {
+            return Err(ControllerError::NotConnected(a, b));
+        }
+        a_connections.retain(|id| *id != b);
+        updated
+            .connected_ids_mut(b)
+            .ok_or(ControllerError::NodeNotFound(b))?
+            .retain(|id| *id != a);
+        updated.links.retain(|link| !link.connects(a, b));
+
+        updated.check_topology()?;
+        updated.check_reachability_from(&topology)?;
+
+        if updated.is_cut(a, b) {
+            // The nodes already stopped using the link, only the held senders are dropped
+            let mut channels = self.channels.write().expect("Controller lock poisoned");
+            channels.link_senders.remove(&(a, b));
+            channels.link_senders.remove(&(b, a));
+        } else {
+            let a_sender = self.link_sender(a, b);
+            self.remove_sender(a, b)?;
+            if let Err(err) = self.remove_sender(b, a) {
+                // Give the first side its sender back, the nodes must match the topology
+                let _ = match a_sender {
+                    Some(sender) => self.restore_sender(a, b, sender),
+                    None => self.add_sender(a, b),
+                };
+                return Err(err);
+            }
+        }
+
+        *topology = updated;
+        self.sync_manifest(&topology, &[a, b]);
+        Ok(())
+    }
+
+    /// Crash a drone after removing it from its neighbours
+    /// # Errors
+    /// Returns an error if the node is not a drone, the topology without it is invalid
+    /// or one of the nodes cannot be reached
+    pub fn crash_node(&self, id: NodeId) -> Result<(), ControllerError> {
+        let mut topology = self.topology.write().expect("Topology lock poisoned");
+        let mut updated = topology.clone();
+
+        let Some(index) = updated.drones.iter().position(|d| d.id == id) else {
+            return Err(match updated.connected_ids(id) {
+                Some(_) => ControllerError::NotADrone(id),
+                None => ControllerError::NodeNotFound(id),
+            });
+        };
+        let neighbours = updated.drones.remove(index).connected_drone_ids;
+        for neighbour in &neighbours {
+            if let Some(connections) = updated.connected_ids_mut(*neighbour) {
+                connections.retain(|n| *n != id);
+            }
+        }
+        updated
+            .links
+            .retain(|link| link.from != id && link.to != id);
+
+        updated.check_topology()?;
+        updated.check_reachability_from(&topology)?;
+
+        let mut removed = Vec::new();
+        let mut result = Ok(());
+        for neighbour in &neighbours {
+            let sender = self.link_sender(*neighbour, id);
+            result = self.remove_sender(*neighbour, id);
+            if result.is_err() {
+                break;
+            }
+            removed.push((*neighbour, sender));
+        }
+        if let Err(err) = result.and_then(|()| self.crash(id)) {
+            // Give the neighbours their senders back, the nodes must match the topology
+            for (neighbour, sender) in removed {
+                let _ = match sender {
+                    Some(sender) if topology.is_cut(neighbour, id) => {
+                        self.channels
+                            .write()
+                            .expect("Controller lock poisoned")
+                            .link_senders
+                            .insert((neighbour, id), sender);
+                        Ok(())
+                    }
+                    Some(sender) => self.restore_sender(neighbour, id, sender),
+                    None => self.add_sender(neighbour, id),
+                };
+            }
+            return Err(err);
+        }
+
+        {
+            let mut channels = self.channels.write().expect("Controller lock poisoned");
+            channels.packet_senders.remove(&id);
+            channels.command_senders.remove(&id);
+            channels
+                .link_senders
+                .retain(|(from, to), _| *from != id && *to != id);
+        }
+
+        *topology = updated;
+        self.sync_manifest(&topology, &neighbours);
+        self.manifest.update(|manifest| {
+            if let Some(entry) = manifest.get_mut(id) {
+                entry.neighbours.clear();
+            }
+        });
+        Ok(())
+    }
+
+    /// Add a new drone to the running network, connected to `neighbours`.
+    /// Its thread is supervised with the default restart policy of the initializer
+    /// # Errors
+    /// Returns an error if `pdr` is not in `[0, 1]`, the id is already used, the resulting topology
+    /// is invalid, `drone_type` is not available or one of the neighbours does not exist
+    pub fn spawn_drone(
+        &self,
+        id: NodeId,
+        drone_type: DroneType,
+        pdr: f32,
+        neighbours: &[NodeId],
+    ) -> Result<(), ControllerError> {
+        if !(0.0..=1.0).contains(&pdr) {
+            return Err(ControllerError::InvalidPdr(pdr));
+        }
+
+        let mut topology = self.topology.write().expect("Topology lock poisoned");
+        let mut updated = topology.clone();
+
+        for neighbour in neighbours {
+            updated
+                .connected_ids_mut(*neighbour)
+                .ok_or(ControllerError::NodeNotFound(*neighbour))?
+                .push(id);
+        }
+        let drone = ParsedDrone {
+            id,
+            connected_drone_ids: neighbours.to_vec(),
+            pdr,
+            channel_capacity: None,
+        };
+        updated.drones.push(drone.clone());
+
+        updated.check_topology()?;
+        updated.check_reachability_from(&topology)?;
+
+        self.spawner
+            .spawn(&drone, drone_type)
+            .ok_or(ControllerError::UnavailableDroneType(drone_type))?;
+
+        *topology = updated;
+        self.manifest.update(|manifest| {
+            manifest.nodes.push(NodeManifestEntry {
+                id,
+                kind: NodeType::Drone,
+                implementation: drone_type.name(),
+                pdr: Some(pdr),
+                neighbours: neighbours.to_vec(),
+                asset_path: None,
+            });
+        });
+        self.sync_manifest(&topology, neighbours);
+        Ok(())
+    }
+
+    /// Copy the connections of `ids` from the topology to the manifest
+    fn sync_manifest(&self, topology: &Parser, ids: &[NodeId]) {
+        self.manifest.update(|manifest| {
+            for id in ids {
+                if let (Some(entry), Some(connections)) =
+                    (manifest.get_mut(*id), topology.connected_ids(*id))
+                {
+                    entry.neighbours.clone_from(connections);
+                }
+            }
+        });
+    }
+
+    /// Cut every link between nodes of different groups, nodes not listed keep all their links.
+    /// The cut links stay in the topology, marked by its partition, and their senders are held back
+    /// so that `heal()` gives the nodes their original links. While the network is partitioned
+    /// the topology changes only have to keep the client-server pairs that are still connected
+    /// # Errors
+    /// Returns an error if the network is already partitioned, there are less than two groups,
+    /// a group is empty, a node is in more than one group or does not exist
+    pub fn partition(&self, groups: &[Vec<NodeId>]) -> Result<(), ControllerError> {
+        let mut topology = self.topology.write().expect("Topology lock poisoned");
+        if topology.partition.is_some() {
+            return Err(ControllerError::AlreadyPartitioned);
+        }
+        if groups.len() < 2 || groups.iter().any(Vec::is_empty) {
+            return Err(ControllerError::InvalidPartition);
+        }
+
+        let mut listed = HashSet::new();
+        for id in groups.iter().flatten() {
+            if topology.connected_ids(*id).is_none() {
+                return Err(ControllerError::NodeNotFound(*id));
+            }
+            if !listed.insert(*id) {
+                return Err(ControllerError::InvalidPartition);
+            }
+        }
+
+        topology.partition = Some(groups.to_vec());
+        for (from, to) in cut_links(&topology) {
+            // The sender stays in the shared channels, a node that cannot be reached has nothing to cut
+            let _ = self.send_command(from, DroneCommand::RemoveSender(to));
+        }
+
+        self.manifest.update(|manifest| {
+            manifest.partition = Some(groups.to_vec());
+        });
+        Ok(())
+    }
+
+    /// Give back the links cut by `partition()` that are still part of the topology,
+    /// using the senders the nodes had before the partition
+    /// # Errors
+    /// Returns an error if the network is not partitioned
+    pub fn heal(&self) -> Result<(), ControllerError> {
+        let mut topology = self.topology.write().expect("Topology lock poisoned");
+        if topology.partition.is_none() {
+            return Err(ControllerError::NotPartitioned);
+        }
+
+        for (from, to) in cut_links(&topology) {
+            let held = self
+                .channels
+                .read()
+                .expect("Controller lock poisoned")
+                .link_senders
+                .get(&(from, to))
+                .cloned();
+            let _ = match held {
+                Some(sender) => self.restore_sender(from, to, sender),
+                None => self.add_sender(from, to),
+            };
+        }
+        topology.partition = None;
+
+        self.manifest.update(|manifest| manifest.partition = None);
+        Ok(())
+    }
+}
+
+/// Returns both directions of every link of the topology cut by its partition
+fn cut_links(topology: &Parser) -> Vec<(NodeId, NodeId)> {
+    let Some(groups) = &topology.partition else {
+        return Vec::new();
+    };
+
+    groups
+        .iter()
+        .flatten()
+        .filter_map(|from| Some((*from, topology.connected_ids(*from)?)))
+        .flat_map(|(from, connections)| connections.iter().map(move |to| (from, *to)))
+        .filter(|(from, to)| topology.is_cut(*from, *to))
+        .collect()
+}
@@ -0,0 +1,7 @@
+pub mod chaos;
+pub mod controller;
+pub mod scenario;
+
+pub use chaos::{ChaosConfig, ChaosEngine};
+pub use controller::SimulationController;
+pub use scenario::Scenario;
@@ -0,0 +1,154 @@
+use std::fmt::{self, Display};
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use wg_internal::network::NodeId;
+
+use crate::errors::{ControllerError, ScenarioError};
+use crate::log_sink::{log_as, log_error_as};
+
+use super::SimulationController;
+
+/// Change applied to the network by a scenario step
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ScenarioAction {
+    Crash { node: NodeId },
+    SetPdr { node: NodeId, pdr: f32 },
+    AddLink { a: NodeId, b: NodeId },
+    RemoveLink { a: NodeId, b: NodeId },
+}
+
+impl ScenarioAction {
+    fn apply(self, controller: &SimulationController) -> Result<(), ControllerError> {
+        match self {
+            ScenarioAction::Crash { node } => controller.crash_node(node),
+            ScenarioAction::SetPdr { node, pdr } => controller.set_packet_drop_rate(node, pdr),
+            ScenarioAction::AddLink { a, b } => controller.add_link(a, b),
+            ScenarioAction::RemoveLink { a, b } => controller.remove_link(a, b),
+        }
+    }
+}
+
+impl Display for ScenarioAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScenarioAction::Crash { node } => write!(f, "crash drone {node}"),
+            ScenarioAction::SetPdr { node, pdr } => write!(f, "set pdr of drone {node} to {pdr}"),
+            ScenarioAction::AddLink { a, b } => write!(f, "add link {a}-{b}"),
+            ScenarioAction::RemoveLink { a, b } => write!(f, "remove link {a}-{b}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct ScenarioStep {
+    /// Time of the step since the start of the scenario
+    pub at_ms: u64,
+    #[serde(flatten)]
+    pub action: ScenarioAction,
+}
+
+/// Timeline of changes applied to a running simulation
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Scenario {
+    #[serde(default)]
+    pub steps: Vec<ScenarioStep>,
+}
+
+impl Scenario {
+    /// Load a scenario, its steps are sorted by time
+    /// # Errors
+    /// Returns an error if the file cannot be read or parsed
+    pub fn load(path: &str) -> Result<Self, ScenarioError> {
+        let data =
+            fs::read_to_string(path).map_err(|_| ScenarioError::FileReadError(path.to_string()))?;
+        let mut scenario: Scenario =
+            toml::from_str(&data).map_err(|_| ScenarioError::ParseError(path.to_string()))?;
+
+        scenario.steps.sort_by_key(|step| step.at_ms);
+        Ok(scenario)
+    }
+
+    /// Apply every step at its time through `controller`, blocking until the last one.
+    /// Each step is validated against the topology at the time it is applied,
+    /// a failed step is reported and does not stop the scenario
+    #[must_use]
+    pub fn execute(&self, controller: &SimulationController) -> ScenarioReport {
+        let start = Instant::now();
+        let mut outcomes = Vec::with_capacity(self.steps.len());
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let due = start + Duration::from_millis(step.at_ms);
+            thread::sleep(due.saturating_duration_since(Instant::now()));
+
+            let result = step.action.apply(controller);
+            match &result {
+                Ok(()) => log_as(
+                    "scenario",
+                    format_args!("t={}ms {}", step.at_ms, step.action),
+                ),
+                Err(err) => log_error_as(
+                    "scenario",
+                    format_args!("t={}ms {} failed: {err}", step.at_ms, step.action),
+                ),
+            }
+
+            outcomes.push(StepOutcome {
+                index,
+                step: *step,
+                result,
+            });
+        }
+
+        ScenarioReport { outcomes }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct StepOutcome {
+    /// Position of the step in the sorted scenario
+    pub index: usize,
+    pub step: ScenarioStep,
+    pub result: Result<(), ControllerError>,
+}
+
+/// Outcome of every step of an executed scenario
+#[derive(Debug, Default, PartialEq)]
+pub struct ScenarioReport {
+    pub outcomes: Vec<StepOutcome>,
+}
+
+impl ScenarioReport {
+    #[must_use]
+    pub fn all_applied(&self) -> bool {
+        self.outcomes.iter().all(|outcome| outcome.result.is_ok())
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &StepOutcome> {
+        self.outcomes
+            .iter()
+            .filter(|outcome| outcome.result.is_err())
+    }
+}
+
+impl Display for ScenarioReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let applied = self.outcomes.iter().filter(|o| o.result.is_ok()).count();
+        writeln!(f, "Steps applied: {applied}/{}", self.outcomes.len())?;
+        for outcome in &self.outcomes {
+            write!(
+                f,
+                "#{} t={}ms {}: ",
+                outcome.index, outcome.step.at_ms, outcome.step.action
+            )?;
+            match &outcome.result {
+                Ok(()) => writeln!(f, "applied")?,
+                Err(err) => writeln!(f, "failed ({err})")?,
+            }
+        }
+        Ok(())
+    }
+}
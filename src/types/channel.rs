@@ -1,4 +1,5 @@
-use crossbeam::channel::{unbounded, Receiver, Sender};
+use crossbeam::channel::{bounded, unbounded, Receiver, Sender};
+use serde::Deserialize;
 
 #[derive(Debug, Clone)]
 pub struct Channel<T> {
@@ -11,6 +12,16 @@ impl<T> Channel<T> {
     pub fn new(sender: Sender<T>, receiver: Receiver<T>) -> Self {
         Channel { sender, receiver }
     }
+
+    /// Create a channel holding at most `capacity` messages, unbounded if `capacity` is `None`
+    #[must_use]
+    pub fn with_capacity(capacity: Option<usize>) -> Self {
+        let (tx, rx) = match capacity {
+            Some(capacity) => bounded(capacity),
+            None => unbounded(),
+        };
+        Channel::new(tx, rx)
+    }
 }
 
 impl<T> Default for Channel<T> {
@@ -22,3 +33,14 @@ impl<T> Default for Channel<T> {
         }
     }
 }
+
+/// What happens when a packet is sent to a full bounded channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// The sender waits until there is room in the channel
+    #[default]
+    Block,
+    /// The packet is discarded and reported as `SimulationEvent::LinkDropped`
+    Drop,
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use wg_internal::network::NodeId;
+use wg_internal::packet::{Packet, PacketType};
+
+/// Snapshot of the traffic that went through the link from `from` to `to`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinkStats {
+    pub from: NodeId,
+    pub to: NodeId,
+    pub msg_fragments: u64,
+    pub acks: u64,
+    pub nacks: u64,
+    pub flood_requests: u64,
+    pub flood_responses: u64,
+    /// Sum of the `length` of the forwarded fragments
+    pub fragment_bytes: u64,
+    /// Packets lost on the link or discarded because the receiving channel was full
+    pub dropped: u64,
+    /// Packets waiting in the link and in the queue of the receiving node after the last delivery
+    pub queue_len: usize,
+    pub peak_queue_len: usize,
+}
+
+impl LinkStats {
+    #[must_use]
+    pub fn total_packets(&self) -> u64 {
+        self.msg_fragments + self.acks + self.nacks + self.flood_requests + self.flood_responses
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct LinkCounters {
+    msg_fragments: AtomicU64,
+    acks: AtomicU64,
+    nacks: AtomicU64,
+    flood_requests: AtomicU64,
+    flood_responses: AtomicU64,
+    fragment_bytes: AtomicU64,
+    dropped: AtomicU64,
+    queue_len: AtomicUsize,
+    peak_queue_len: AtomicUsize,
+}
+
+impl LinkCounters {
+    pub(crate) fn record_packet(&self, packet: &Packet) {
+        let counter = match &packet.pack_type {
+            PacketType::MsgFragment(fragment) => {
+                self.fragment_bytes
+                    .fetch_add(u64::from(fragment.length), Ordering::Relaxed);
+                &self.msg_fragments
+            }
+            PacketType::Ack(_) => &self.acks,
+            PacketType::Nack(_) => &self.nacks,
+            PacketType::FloodRequest(_) => &self.flood_requests,
+            PacketType::FloodResponse(_) => &self.flood_responses,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_drop(&self) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_queue_len(&self, queue_len: usize) {
+        self.queue_len.store(queue_len, Ordering::Relaxed);
+        self.peak_queue_len.fetch_max(queue_len, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self, from: NodeId, to: NodeId) -> LinkStats {
+        LinkStats {
+            from,
+            to,
+            msg_fragments: self.msg_fragments.load(Ordering::Relaxed),
+            acks: self.acks.load(Ordering::Relaxed),
+            nacks: self.nacks.load(Ordering::Relaxed),
+            flood_requests: self.flood_requests.load(Ordering::Relaxed),
+            flood_responses: self.flood_responses.load(Ordering::Relaxed),
+            fragment_bytes: self.fragment_bytes.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            queue_len: self.queue_len.load(Ordering::Relaxed),
+            peak_queue_len: self.peak_queue_len.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Counters of every instrumented link, it can be cloned and read while the simulation is running
+#[derive(Debug, Clone, Default)]
+pub struct LinkStatsHandle(Arc<Mutex<HashMap<(NodeId, NodeId), Arc<LinkCounters>>>>);
+
+impl LinkStatsHandle {
+    /// Returns the counters of the link, creating them the first time it is used.
+    /// A link that is connected again (e.g. after a restart) keeps counting on the same counters.
+    pub(crate) fn counters(&self, from: NodeId, to: NodeId) -> Arc<LinkCounters> {
+        let mut links = self.0.lock().expect("Link stats lock poisoned");
+        Arc::clone(links.entry((from, to)).or_default())
+    }
+
+    /// Returns the stats of every link sorted by `(from, to)`
+    /// # Panics
+    /// Panics if the lock is poisoned
+    #[must_use]
+    pub fn snapshot(&self) -> Vec<LinkStats> {
+        let links = self.0.lock().expect("Link stats lock poisoned");
+        let mut stats: Vec<LinkStats> = links
+            .iter()
+            .map(|((from, to), counters)| counters.snapshot(*from, *to))
+            .collect();
+        stats.sort_by_key(|s| (s.from, s.to));
+        stats
+    }
+}
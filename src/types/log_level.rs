@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+/// Most verbose level of the messages printed by a client or a server.
+/// Drones are not configurable, since the `Drone` trait does not expose their logger.
+/// Every level also enables the less verbose ones, e.g. the default `Info` also prints errors.
+/// Warnings are opt-in, they are printed from `Warning` up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Off,
+    Error,
+    #[default]
+    Info,
+    Warning,
+    Debug,
+}
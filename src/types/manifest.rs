@@ -0,0 +1,113 @@
+use std::fmt::{self, Display};
+use std::sync::{Arc, RwLock};
+
+use wg_internal::network::NodeId;
+
+use super::parsed_nodes::NodeType;
+
+/// Description of a single node as it was instantiated by the network initializer
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeManifestEntry {
+    pub id: NodeId,
+    pub kind: NodeType,
+    /// Name of the implementation running the node (e.g. `RustRoveri`, `ClientVideo`, `Server`)
+    pub implementation: String,
+    /// Packet drop rate, only set for drones
+    pub pdr: Option<f32>,
+    pub neighbours: Vec<NodeId>,
+    /// Initialization folder passed to the node, only set for clients and servers
+    pub asset_path: Option<String>,
+}
+
+/// Mapping between every `NodeId` and the implementation chosen for it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeManifest {
+    /// Seed of every random decision taken by the initializer, reuse it to replay the run
+    pub seed: u64,
+    pub nodes: Vec<NodeManifestEntry>,
+    /// Groups of the current network partition, `None` if the network is not partitioned
+    pub partition: Option<Vec<Vec<NodeId>>>,
+}
+
+impl NodeManifestEntry {
+    /// Name of the thread running the node, e.g. `drone-3-RustRoveri`, `client-20-video` or `server-30`
+    #[must_use]
+    pub fn thread_name(&self) -> String {
+        match self.kind {
+            NodeType::Drone => format!("drone-{}-{}", self.id, self.implementation),
+            NodeType::Client => {
+                let kind = self
+                    .implementation
+                    .strip_prefix("Client")
+                    .unwrap_or(&self.implementation);
+                format!("client-{}-{}", self.id, kind.to_lowercase())
+            }
+            NodeType::Server => format!("server-{}", self.id),
+        }
+    }
+}
+
+impl NodeManifest {
+    #[must_use]
+    pub fn get(&self, id: NodeId) -> Option<&NodeManifestEntry> {
+        self.nodes.iter().find(|entry| entry.id == id)
+    }
+
+    pub(crate) fn get_mut(&mut self, id: NodeId) -> Option<&mut NodeManifestEntry> {
+        self.nodes.iter_mut().find(|entry| entry.id == id)
+    }
+
+    pub fn of_kind(&self, kind: NodeType) -> impl Iterator<Item = &NodeManifestEntry> {
+        self.nodes.iter().filter(move |entry| entry.kind == kind)
+    }
+}
+
+impl Display for NodeManifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Simulation seed: {}", self.seed)?;
+        for entry in &self.nodes {
+            write!(
+                f,
+                "{:?} {} -> {} neighbours: {:?}",
+                entry.kind, entry.id, entry.implementation, entry.neighbours
+            )?;
+            if let Some(pdr) = entry.pdr {
+                write!(f, " pdr: {pdr}")?;
+            }
+            if let Some(path) = &entry.asset_path {
+                write!(f, " assets: {path}")?;
+            }
+            writeln!(f)?;
+        }
+        if let Some(groups) = &self.partition {
+            writeln!(f, "Partitioned in groups: {groups:?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Handle to the manifest of a network, it can be cloned and read from other threads
+/// while `run_simulation` is blocking
+#[derive(Debug, Clone, Default)]
+pub struct ManifestHandle(Arc<RwLock<Option<NodeManifest>>>);
+
+impl ManifestHandle {
+    /// Returns a copy of the manifest, `None` if the network has not been initialized yet
+    /// # Panics
+    /// Panics if the lock is poisoned
+    #[must_use]
+    pub fn get(&self) -> Option<NodeManifest> {
+        self.0.read().expect("Manifest lock poisoned").clone()
+    }
+
+    pub(crate) fn set(&self, manifest: NodeManifest) {
+        *self.0.write().expect("Manifest lock poisoned") = Some(manifest);
+    }
+
+    /// Apply `update` to the manifest if the network has been initialized
+    pub(crate) fn update<F: FnOnce(&mut NodeManifest)>(&self, update: F) {
+        if let Some(manifest) = self.0.write().expect("Manifest lock poisoned").as_mut() {
+            update(manifest);
+        }
+    }
+}
@@ -1,2 +1,7 @@
 pub mod channel;
+pub mod link_stats;
+pub mod log_level;
+pub mod manifest;
+pub mod parsed_links;
 pub mod parsed_nodes;
+pub mod simulation_event;
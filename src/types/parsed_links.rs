@@ -0,0 +1,61 @@
+use serde::Deserialize;
+use wg_internal::network::NodeId;
+
+/// Properties of the connection between two nodes, applied in both directions
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParsedLink {
+    pub from: NodeId,
+    pub to: NodeId,
+    /// Time taken by a packet to cross the link
+    #[serde(default)]
+    pub latency_ms: u64,
+    /// Maximum deviation from `latency_ms`, packets are never reordered
+    #[serde(default)]
+    pub jitter_ms: u64,
+    /// Rate at which fragment payloads are transmitted, unlimited if not set
+    pub bandwidth_kbps: Option<u64>,
+    /// Loss applied to the fragments crossing the link, on top of the drone PDR
+    pub loss: Option<LossModel>,
+}
+
+/// How the fragments crossing a link are lost
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LossModel {
+    /// Every fragment is lost with the same `probability`
+    Uniform { probability: f64 },
+    /// Two-state burst model: the link moves between a good and a bad state before each fragment,
+    /// and loses it with the probability of the current state
+    GilbertElliott {
+        p_good_to_bad: f64,
+        p_bad_to_good: f64,
+        loss_good: f64,
+        loss_bad: f64,
+    },
+}
+
+impl ParsedLink {
+    #[must_use]
+    pub fn connects(&self, a: NodeId, b: NodeId) -> bool {
+        (self.from == a && self.to == b) || (self.from == b && self.to == a)
+    }
+}
+
+impl LossModel {
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        let is_probability = |p: f64| (0.0..=1.0).contains(&p);
+
+        match *self {
+            LossModel::Uniform { probability } => is_probability(probability),
+            LossModel::GilbertElliott {
+                p_good_to_bad,
+                p_bad_to_good,
+                loss_good,
+                loss_bad,
+            } => [p_good_to_bad, p_bad_to_good, loss_good, loss_bad]
+                .into_iter()
+                .all(is_probability),
+        }
+    }
+}
@@ -1,30 +1,42 @@
 use serde::Deserialize;
 use wg_internal::network::NodeId;
 
-#[derive(PartialEq)]
+use super::log_level::LogLevel;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NodeType {
     Drone,
     Client,
     Server,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct ParsedDrone {
     pub id: NodeId,
     pub connected_drone_ids: Vec<NodeId>,
     pub pdr: f32,
+    /// Capacity of the channel receiving the packets of this node, overrides the global one
+    pub channel_capacity: Option<usize>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct ParsedClient {
     pub id: NodeId,
     pub connected_drone_ids: Vec<NodeId>,
+    /// Capacity of the channel receiving the packets of this node, overrides the global one
+    pub channel_capacity: Option<usize>,
+    /// Log level of this node, overrides the global one
+    pub log_level: Option<LogLevel>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct ParsedServer {
     pub id: NodeId,
     pub connected_drone_ids: Vec<NodeId>,
+    /// Capacity of the channel receiving the packets of this node, overrides the global one
+    pub channel_capacity: Option<usize>,
+    /// Log level of this node, overrides the global one
+    pub log_level: Option<LogLevel>,
 }
 
 pub trait Initializable {
@@ -66,6 +78,10 @@ pub trait Node {
     fn id(&self) -> NodeId;
     fn connected_drone_ids(&self) -> &Vec<NodeId>;
     fn node_type(&self) -> NodeType;
+    fn channel_capacity(&self) -> Option<usize>;
+    fn log_level(&self) -> Option<LogLevel> {
+        None
+    }
 }
 
 impl Node for ParsedDrone {
@@ -80,6 +96,10 @@ impl Node for ParsedDrone {
     fn node_type(&self) -> NodeType {
         NodeType::Drone
     }
+
+    fn channel_capacity(&self) -> Option<usize> {
+        self.channel_capacity
+    }
 }
 
 impl Node for ParsedClient {
@@ -94,6 +114,14 @@ impl Node for ParsedClient {
     fn node_type(&self) -> NodeType {
         NodeType::Client
     }
+
+    fn channel_capacity(&self) -> Option<usize> {
+        self.channel_capacity
+    }
+
+    fn log_level(&self) -> Option<LogLevel> {
+        self.log_level
+    }
 }
 
 impl Node for ParsedServer {
@@ -108,4 +136,12 @@ impl Node for ParsedServer {
     fn node_type(&self) -> NodeType {
         NodeType::Server
     }
+
+    fn channel_capacity(&self) -> Option<usize> {
+        self.channel_capacity
+    }
+
+    fn log_level(&self) -> Option<LogLevel> {
+        self.log_level
+    }
 }
@@ -0,0 +1,39 @@
+use wg_internal::{controller::DroneEvent, network::NodeId};
+
+use crate::simulation::scenario::ScenarioStep;
+
+/// Events generated by the network initializer itself, as opposed to the `DroneEvent`s sent by the nodes
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimulationEvent {
+    /// The thread of a node panicked, `message` is the panic payload when it is a string
+    NodePanicked { id: NodeId, message: String },
+    /// A drone was restarted after a panic, `restarts` counts the restarts done so far
+    NodeRestarted { id: NodeId, restarts: u32 },
+    /// The chaos engine applied a change, the `at_ms` of the step is the time since the engine started running
+    ChaosApplied(ScenarioStep),
+    /// A packet of `session_id` was discarded by the link from `from` to `to`, not by a drone
+    LinkDropped {
+        from: NodeId,
+        to: NodeId,
+        session_id: u64,
+        cause: LinkDropCause,
+    },
+}
+
+/// Why a link discarded a packet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkDropCause {
+    /// The channel of the receiving node was full and the overflow policy is `drop`
+    Overflow,
+    /// The fragment was lost by the loss model of the link
+    Loss,
+}
+
+/// Event delivered by the simulation controller
+#[derive(Debug)]
+pub enum ControllerEvent {
+    /// Event sent by a node, shortcuts have already been delivered
+    Node(DroneEvent),
+    /// Event generated by the initializer
+    Simulation(SimulationEvent),
+}
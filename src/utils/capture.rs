@@ -0,0 +1,311 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crossbeam::channel::{unbounded, Sender};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use wg_internal::network::{NodeId, SourceRoutingHeader};
+use wg_internal::packet::{
+    Ack, FloodRequest, FloodResponse, Fragment, Nack, NackType, NodeType, Packet, PacketType,
+    FRAGMENT_DSIZE,
+};
+
+use super::errors::CaptureError;
+
+/// A packet seen on the link from `from` to `to`, one per line in a capture file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapturedPacket {
+    /// Microseconds since the UNIX epoch
+    pub timestamp_us: u64,
+    pub from: NodeId,
+    pub to: NodeId,
+    /// The link lost the packet, it never reached `to`
+    pub lost_on_link: bool,
+    pub session_id: u64,
+    pub hop_index: usize,
+    pub hops: Vec<NodeId>,
+    pub pack_type: CapturedPacketType,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CapturedPacketType {
+    MsgFragment {
+        fragment_index: u64,
+        total_n_fragments: u64,
+        data: Vec<u8>,
+    },
+    Ack {
+        fragment_index: u64,
+    },
+    Nack {
+        fragment_index: u64,
+        nack_type: CapturedNackType,
+    },
+    FloodRequest {
+        flood_id: u64,
+        initiator_id: NodeId,
+        path_trace: Vec<(NodeId, CapturedNodeType)>,
+    },
+    FloodResponse {
+        flood_id: u64,
+        path_trace: Vec<(NodeId, CapturedNodeType)>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapturedNackType {
+    ErrorInRouting(NodeId),
+    DestinationIsDrone,
+    Dropped,
+    UnexpectedRecipient(NodeId),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapturedNodeType {
+    Client,
+    Drone,
+    Server,
+}
+
+impl From<&NackType> for CapturedNackType {
+    fn from(nack_type: &NackType) -> Self {
+        match nack_type {
+            NackType::ErrorInRouting(id) => CapturedNackType::ErrorInRouting(*id),
+            NackType::DestinationIsDrone => CapturedNackType::DestinationIsDrone,
+            NackType::Dropped => CapturedNackType::Dropped,
+            NackType::UnexpectedRecipient(id) => CapturedNackType::UnexpectedRecipient(*id),
+        }
+    }
+}
+
+impl From<CapturedNackType> for NackType {
+    fn from(nack_type: CapturedNackType) -> Self {
+        match nack_type {
+            CapturedNackType::ErrorInRouting(id) => NackType::ErrorInRouting(id),
+            CapturedNackType::DestinationIsDrone => NackType::DestinationIsDrone,
+            CapturedNackType::Dropped => NackType::Dropped,
+            CapturedNackType::UnexpectedRecipient(id) => NackType::UnexpectedRecipient(id),
+        }
+    }
+}
+
+impl From<&NodeType> for CapturedNodeType {
+    fn from(node_type: &NodeType) -> Self {
+        match node_type {
+            NodeType::Client => CapturedNodeType::Client,
+            NodeType::Drone => CapturedNodeType::Drone,
+            NodeType::Server => CapturedNodeType::Server,
+        }
+    }
+}
+
+impl From<CapturedNodeType> for NodeType {
+    fn from(node_type: CapturedNodeType) -> Self {
+        match node_type {
+            CapturedNodeType::Client => NodeType::Client,
+            CapturedNodeType::Drone => NodeType::Drone,
+            CapturedNodeType::Server => NodeType::Server,
+        }
+    }
+}
+
+impl From<&PacketType> for CapturedPacketType {
+    fn from(pack_type: &PacketType) -> Self {
+        match pack_type {
+            PacketType::MsgFragment(fragment) => CapturedPacketType::MsgFragment {
+                fragment_index: fragment.fragment_index,
+                total_n_fragments: fragment.total_n_fragments,
+                // The length comes from the sender and may exceed the payload
+                data: fragment.data[..usize::from(fragment.length).min(FRAGMENT_DSIZE)].to_vec(),
+            },
+            PacketType::Ack(ack) => CapturedPacketType::Ack {
+                fragment_index: ack.fragment_index,
+            },
+            PacketType::Nack(nack) => CapturedPacketType::Nack {
+                fragment_index: nack.fragment_index,
+                nack_type: CapturedNackType::from(&nack.nack_type),
+            },
+            PacketType::FloodRequest(flood_request) => CapturedPacketType::FloodRequest {
+                flood_id: flood_request.flood_id,
+                initiator_id: flood_request.initiator_id,
+                path_trace: capture_path_trace(&flood_request.path_trace),
+            },
+            PacketType::FloodResponse(flood_response) => CapturedPacketType::FloodResponse {
+                flood_id: flood_response.flood_id,
+                path_trace: capture_path_trace(&flood_response.path_trace),
+            },
+        }
+    }
+}
+
+impl From<&CapturedPacketType> for PacketType {
+    fn from(pack_type: &CapturedPacketType) -> Self {
+        match pack_type {
+            CapturedPacketType::MsgFragment {
+                fragment_index,
+                total_n_fragments,
+                data,
+            } => {
+                let length = data.len().min(FRAGMENT_DSIZE);
+                let mut fragment_data = [0; FRAGMENT_DSIZE];
+                fragment_data[..length].copy_from_slice(&data[..length]);
+
+                PacketType::MsgFragment(Fragment {
+                    fragment_index: *fragment_index,
+                    total_n_fragments: *total_n_fragments,
+                    length: u8::try_from(length).unwrap_or(u8::MAX),
+                    data: fragment_data,
+                })
+            }
+            CapturedPacketType::Ack { fragment_index } => PacketType::Ack(Ack {
+                fragment_index: *fragment_index,
+            }),
+            CapturedPacketType::Nack {
+                fragment_index,
+                nack_type,
+            } => PacketType::Nack(Nack {
+                fragment_index: *fragment_index,
+                nack_type: NackType::from(*nack_type),
+            }),
+            CapturedPacketType::FloodRequest {
+                flood_id,
+                initiator_id,
+                path_trace,
+            } => PacketType::FloodRequest(FloodRequest {
+                flood_id: *flood_id,
+                initiator_id: *initiator_id,
+                path_trace: restore_path_trace(path_trace),
+            }),
+            CapturedPacketType::FloodResponse {
+                flood_id,
+                path_trace,
+            } => PacketType::FloodResponse(FloodResponse {
+                flood_id: *flood_id,
+                path_trace: restore_path_trace(path_trace),
+            }),
+        }
+    }
+}
+
+fn capture_path_trace(path_trace: &[(NodeId, NodeType)]) -> Vec<(NodeId, CapturedNodeType)> {
+    path_trace
+        .iter()
+        .map(|(id, node_type)| (*id, CapturedNodeType::from(node_type)))
+        .collect()
+}
+
+fn restore_path_trace(path_trace: &[(NodeId, CapturedNodeType)]) -> Vec<(NodeId, NodeType)> {
+    path_trace
+        .iter()
+        .map(|(id, node_type)| (*id, NodeType::from(*node_type)))
+        .collect()
+}
+
+/// Microseconds since the UNIX epoch
+pub(crate) fn now_us() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX))
+        .unwrap_or_default()
+}
+
+impl CapturedPacket {
+    #[must_use]
+    pub fn new(from: NodeId, to: NodeId, packet: &Packet) -> Self {
+        CapturedPacket {
+            timestamp_us: now_us(),
+            from,
+            to,
+            lost_on_link: false,
+            session_id: packet.session_id,
+            hop_index: packet.routing_header.hop_index,
+            hops: packet.routing_header.hops.clone(),
+            pack_type: CapturedPacketType::from(&packet.pack_type),
+        }
+    }
+
+    /// Rebuild the original packet
+    #[must_use]
+    pub fn to_packet(&self) -> Packet {
+        Packet {
+            routing_header: SourceRoutingHeader {
+                hop_index: self.hop_index,
+                hops: self.hops.clone(),
+            },
+            session_id: self.session_id,
+            pack_type: PacketType::from(&self.pack_type),
+        }
+    }
+}
+
+/// Create the file at `path` and write every value sent on the returned channel as a JSON line
+pub(crate) fn json_lines_writer<T: Serialize + Send + 'static>(
+    path: &str,
+) -> Result<Sender<T>, CaptureError> {
+    let file = File::create(path).map_err(|_| CaptureError::FileError(path.to_string()))?;
+    let (sender, receiver) = unbounded::<T>();
+
+    thread::spawn(move || {
+        let mut writer = BufWriter::new(file);
+
+        for value in &receiver {
+            let Ok(line) = serde_json::to_string(&value) else {
+                continue;
+            };
+            if writeln!(writer, "{line}").is_err() {
+                break;
+            }
+            // Flush as soon as there is nothing else to write, so that the file is usable at any time
+            if receiver.is_empty() && writer.flush().is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(sender)
+}
+
+/// Read a file with a JSON value per line, empty lines are skipped
+pub(crate) fn read_json_lines<T: DeserializeOwned>(path: &str) -> Result<Vec<T>, CaptureError> {
+    let file = File::open(path).map_err(|_| CaptureError::FileError(path.to_string()))?;
+
+    BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(line) if line.trim().is_empty()))
+        .map(|(i, line)| {
+            let line = line.map_err(|_| CaptureError::FileError(path.to_string()))?;
+            serde_json::from_str(&line).map_err(|_| CaptureError::InvalidRecord(i + 1))
+        })
+        .collect()
+}
+
+/// Sending side of a capture file, every packet is written by a dedicated thread
+#[derive(Debug, Clone)]
+pub(crate) struct CaptureWriter {
+    sender: Sender<CapturedPacket>,
+}
+
+impl CaptureWriter {
+    /// Create the capture file, overwriting it if it exists
+    pub(crate) fn create(path: &str) -> Result<Self, CaptureError> {
+        Ok(CaptureWriter {
+            sender: json_lines_writer(path)?,
+        })
+    }
+
+    pub(crate) fn write(&self, packet: CapturedPacket) {
+        let _ = self.sender.send(packet);
+    }
+}
+
+/// Load a capture file
+/// # Errors
+/// Returns an error if the file cannot be read or a line is not a valid captured packet
+pub fn read_capture(path: &str) -> Result<Vec<CapturedPacket>, CaptureError> {
+    read_json_lines(path)
+}
@@ -1,6 +1,8 @@
 use thiserror::Error;
 use wg_internal::network::NodeId;
 
+use crate::DroneType;
+
 #[derive(Debug, Error, PartialEq)]
 pub enum ConfigError {
     #[error("Unable to read config file {0}")]
@@ -29,6 +31,21 @@ pub enum ConfigError {
 
     #[error("Client {0} connected to server {1}")]
     ClientConnectedToServer(NodeId, NodeId),
+
+    #[error("Channel capacity must be greater than 0")]
+    ZeroChannelCapacity,
+
+    #[error("Invalid link between {0} and {1}")]
+    InvalidLink(NodeId, NodeId),
+
+    #[error("Client {0} cannot reach server {1}")]
+    UnreachableServer(NodeId, NodeId),
+
+    #[error("Invalid topology generator configuration")]
+    InvalidTopologyConfig,
+
+    #[error("The configuration cannot change once the channels are handed out")]
+    AlreadyInitialized,
 }
 
 #[derive(Debug, Error)]
@@ -36,3 +53,113 @@ pub enum ChannelError {
     #[error("Channel not found for node {0}")]
     ChannelNotFound(NodeId),
 }
+
+#[derive(Debug, Error, PartialEq)]
+pub enum CaptureError {
+    #[error("Unable to access capture file {0}")]
+    FileError(String),
+
+    #[error("Invalid captured packet at line {0}")]
+    InvalidRecord(usize),
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ControllerError {
+    #[error("Node {0} not found")]
+    NodeNotFound(NodeId),
+
+    #[error("Node {0} is not reachable")]
+    NodeUnreachable(NodeId),
+
+    #[error("Invalid packet drop rate {0}")]
+    InvalidPdr(f32),
+
+    #[error("Node {0} is not a drone")]
+    NotADrone(NodeId),
+
+    #[error("Nodes {0} and {1} are already connected")]
+    AlreadyConnected(NodeId, NodeId),
+
+    #[error("Nodes {0} and {1} are not connected")]
+    NotConnected(NodeId, NodeId),
+
+    #[error("Invalid topology: {0}")]
+    InvalidTopology(#[from] ConfigError),
+
+    #[error("Drone type {0:?} is not available")]
+    UnavailableDroneType(DroneType),
+
+    #[error("Invalid partition, groups must be at least two, not empty and disjoint")]
+    InvalidPartition,
+
+    #[error("The network is already partitioned")]
+    AlreadyPartitioned,
+
+    #[error("The network is not partitioned")]
+    NotPartitioned,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ChaosError {
+    #[error("Invalid PDR range {0}..={1}")]
+    InvalidPdrRange(f32, f32),
+
+    #[error("Servers need at least 2 connections, got a minimum of {0}")]
+    InvalidServerDegree(usize),
+
+    #[error("Invalid number of actions {0}")]
+    InvalidActions(usize),
+
+    #[error("The action weights must not be all zero nor overflow")]
+    InvalidWeights,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ScenarioError {
+    #[error("Unable to read scenario file {0}")]
+    FileReadError(String),
+
+    #[error("Unable to parse scenario file {0}")]
+    ParseError(String),
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AssetError {
+    #[error("Missing asset directory {0}")]
+    MissingDirectory(String),
+
+    #[error("Missing asset file {0}")]
+    MissingFile(String),
+
+    #[error("Unable to parse metadata file {0}")]
+    InvalidMetadata(String),
+
+    #[error("Missing {path} for title {title}")]
+    MissingContent { path: String, title: String },
+
+    #[error("Playlist {0} has no segments")]
+    EmptyPlaylist(String),
+
+    #[error("Playlist {playlist} references missing segment {segment}")]
+    MissingSegment { playlist: String, segment: String },
+
+    #[error("Unable to write asset {0}")]
+    WriteError(String),
+
+    #[error("Too many songs and videos to give each a unique id")]
+    ContentIdOverflow,
+
+    #[error("Title {title} is published by both node {first} and node {second}")]
+    DuplicateTitle {
+        title: String,
+        first: NodeId,
+        second: NodeId,
+    },
+
+    #[error("Content id {id} is used by both node {first} and node {second}")]
+    DuplicateContentId {
+        id: u16,
+        first: NodeId,
+        second: NodeId,
+    },
+}
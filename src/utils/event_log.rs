@@ -0,0 +1,148 @@
+use std::thread;
+use std::time::Duration;
+
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use serde::{Deserialize, Serialize};
+use wg_internal::controller::DroneEvent;
+use wg_internal::network::{NodeId, SourceRoutingHeader};
+use wg_internal::packet::{Packet, PacketType};
+
+use super::capture::{json_lines_writer, now_us, read_json_lines, CapturedPacketType};
+use super::errors::CaptureError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordedEventKind {
+    PacketSent,
+    PacketDropped,
+    ControllerShortcut,
+}
+
+/// A `DroneEvent` received by the controller, one per line in an event log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Microseconds since the UNIX epoch
+    pub timestamp_us: u64,
+    pub kind: RecordedEventKind,
+    pub session_id: u64,
+    pub hop_index: usize,
+    pub hops: Vec<NodeId>,
+    pub pack_type: CapturedPacketType,
+}
+
+impl RecordedEvent {
+    #[must_use]
+    pub fn new(event: &DroneEvent) -> Self {
+        let (kind, packet) = match event {
+            DroneEvent::PacketSent(packet) => (RecordedEventKind::PacketSent, packet),
+            DroneEvent::PacketDropped(packet) => (RecordedEventKind::PacketDropped, packet),
+            DroneEvent::ControllerShortcut(packet) => {
+                (RecordedEventKind::ControllerShortcut, packet)
+            }
+        };
+
+        RecordedEvent {
+            timestamp_us: now_us(),
+            kind,
+            session_id: packet.session_id,
+            hop_index: packet.routing_header.hop_index,
+            hops: packet.routing_header.hops.clone(),
+            pack_type: CapturedPacketType::from(&packet.pack_type),
+        }
+    }
+
+    /// Rebuild the original event
+    #[must_use]
+    pub fn to_event(&self) -> DroneEvent {
+        let packet = Packet {
+            routing_header: SourceRoutingHeader {
+                hop_index: self.hop_index,
+                hops: self.hops.clone(),
+            },
+            session_id: self.session_id,
+            pack_type: PacketType::from(&self.pack_type),
+        };
+
+        match self.kind {
+            RecordedEventKind::PacketSent => DroneEvent::PacketSent(packet),
+            RecordedEventKind::PacketDropped => DroneEvent::PacketDropped(packet),
+            RecordedEventKind::ControllerShortcut => DroneEvent::ControllerShortcut(packet),
+        }
+    }
+}
+
+/// How fast a recorded event log is replayed
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ReplayTiming {
+    /// Keep the intervals between the recorded events
+    #[default]
+    Original,
+    /// Divide the recorded intervals by the given factor
+    Speed(f64),
+    /// Send all the events at once
+    Immediate,
+}
+
+/// Record every event received from `events` to `path` and forward it to the returned receiver
+/// # Errors
+/// Returns an error if the event log cannot be created
+pub fn record_events(
+    events: Receiver<DroneEvent>,
+    path: &str,
+) -> Result<Receiver<DroneEvent>, CaptureError> {
+    let log = json_lines_writer::<RecordedEvent>(path)?;
+    let (forward_send, forward_recv) = unbounded();
+
+    thread::spawn(move || {
+        for event in events {
+            let _ = log.send(RecordedEvent::new(&event));
+            // Keep recording even if nobody is listening anymore
+            let _ = forward_send.send(event);
+        }
+    });
+
+    Ok(forward_recv)
+}
+
+/// Load an event log
+/// # Errors
+/// Returns an error if the file cannot be read or a line is not a valid event
+pub fn read_events(path: &str) -> Result<Vec<RecordedEvent>, CaptureError> {
+    read_json_lines(path)
+}
+
+/// Send the events of the log at `path` to `events` from a dedicated thread
+/// # Errors
+/// Returns an error if the event log cannot be loaded
+pub fn replay_events(
+    path: &str,
+    events: Sender<DroneEvent>,
+    timing: ReplayTiming,
+) -> Result<(), CaptureError> {
+    let recorded = read_events(path)?;
+
+    thread::spawn(move || {
+        let mut previous_us = recorded.first().map(|event| event.timestamp_us);
+
+        for event in recorded {
+            let interval_us =
+                previous_us.map_or(0, |previous| event.timestamp_us.saturating_sub(previous));
+            previous_us = Some(event.timestamp_us);
+
+            let interval = Duration::from_micros(interval_us);
+            match timing {
+                ReplayTiming::Original => thread::sleep(interval),
+                ReplayTiming::Speed(speed) if speed > 0.0 => {
+                    thread::sleep(interval.div_f64(speed));
+                }
+                ReplayTiming::Speed(_) | ReplayTiming::Immediate => {}
+            }
+
+            if events.send(event.to_event()).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(())
+}
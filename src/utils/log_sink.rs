@@ -0,0 +1,25 @@
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::thread;
+
+/// Write `message` to stdout prefixed by the name of the current thread, e.g. `[drone-3-RustRoveri] ...`.
+/// Only the messages of the initializer go through the sink, such as the restarts logged from a node thread:
+/// the drones, clients and servers print their own output without any prefix
+pub fn log(message: impl Display) {
+    log_as(thread::current().name().unwrap_or("unnamed"), message);
+}
+
+/// Write `message` to stdout prefixed by `prefix`, the line is never interleaved with other lines
+pub fn log_as(prefix: &str, message: impl Display) {
+    let _ = writeln!(io::stdout().lock(), "[{prefix}] {message}");
+}
+
+/// Write `message` to stderr prefixed by `prefix`
+pub fn log_error_as(prefix: &str, message: impl Display) {
+    let _ = writeln!(io::stderr().lock(), "[{prefix}] {message}");
+}
+
+/// Write `message` to stderr as a warning prefixed by `prefix`
+pub fn log_warning_as(prefix: &str, message: impl Display) {
+    log_error_as(prefix, format_args!("Warning: {message}"));
+}
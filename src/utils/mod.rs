@@ -1,2 +1,6 @@
+pub mod capture;
 pub mod errors;
+pub mod event_log;
+pub mod log_sink;
 pub mod parser;
+pub mod topology_generator;
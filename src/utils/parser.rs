@@ -1,20 +1,36 @@
 use super::errors::ConfigError;
 use crate::{
+    channel::OverflowPolicy,
+    log_level::LogLevel,
+    parsed_links::ParsedLink,
     parsed_nodes::{Node, NodeType},
     types::parsed_nodes::{ParsedClient, ParsedDrone, ParsedServer},
 };
 use serde::Deserialize;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
     fs,
 };
 use wg_internal::network::NodeId;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Parser {
     pub drones: Vec<ParsedDrone>,
     pub clients: Vec<ParsedClient>,
     pub servers: Vec<ParsedServer>,
+    /// Capacity of every node channel, unbounded if not set
+    pub channel_capacity: Option<usize>,
+    #[serde(default)]
+    pub overflow_policy: OverflowPolicy,
+    #[serde(default)]
+    pub links: Vec<ParsedLink>,
+    /// Seed of the simulation, a random one is used if not set
+    pub seed: Option<u64>,
+    /// Log level of every client and server, `info` if not set
+    pub log_level: Option<LogLevel>,
+    /// Groups of the current network partition, the links between nodes of different groups are cut
+    #[serde(skip)]
+    pub partition: Option<Vec<Vec<NodeId>>>,
 }
 
 impl Parser {
@@ -26,6 +42,12 @@ impl Parser {
             drones: Vec::new(),
             clients: Vec::new(),
             servers: Vec::new(),
+            channel_capacity: None,
+            overflow_policy: OverflowPolicy::default(),
+            links: Vec::new(),
+            seed: None,
+            log_level: None,
+            partition: None,
         };
 
         if let Some(path) = path {
@@ -47,10 +69,41 @@ impl Parser {
         self.drones = config.drones;
         self.clients = config.clients;
         self.servers = config.servers;
+        self.channel_capacity = config.channel_capacity;
+        self.overflow_policy = config.overflow_policy;
+        self.links = config.links;
+        self.seed = config.seed;
+        self.log_level = config.log_level;
 
         self.check_topology()
     }
 
+    /// Returns the capacity of the channel receiving the packets of `id`
+    #[must_use]
+    pub fn channel_capacity(&self, id: NodeId) -> Option<usize> {
+        self.drones
+            .iter()
+            .map(|d| d as &dyn Node)
+            .chain(self.clients.iter().map(|c| c as &dyn Node))
+            .chain(self.servers.iter().map(|s| s as &dyn Node))
+            .find(|node| node.id() == id)
+            .and_then(Node::channel_capacity)
+            .or(self.channel_capacity)
+    }
+
+    /// Returns the log level of `id`
+    #[must_use]
+    pub fn log_level(&self, id: NodeId) -> LogLevel {
+        self.clients
+            .iter()
+            .map(|c| c as &dyn Node)
+            .chain(self.servers.iter().map(|s| s as &dyn Node))
+            .find(|node| node.id() == id)
+            .and_then(Node::log_level)
+            .or(self.log_level)
+            .unwrap_or_default()
+    }
+
     fn generic_check_topology<T: Node>(
         nodes: &[T],
         all_ids: &HashSet<NodeId>,
@@ -68,6 +121,10 @@ impl Parser {
                 return Err(ConfigError::ServerWithLessThanTwoConnections(node.id()));
             }
 
+            if node.channel_capacity() == Some(0) {
+                return Err(ConfigError::ZeroChannelCapacity);
+            }
+
             let mut connection_set = HashSet::new();
             for connection in node.connected_drone_ids() {
                 if *connection == node.id()
@@ -92,7 +149,108 @@ impl Parser {
         Ok(())
     }
 
-    fn check_topology(&self) -> Result<(), ConfigError> {
+    /// Returns the connections of the node `id`
+    pub(crate) fn connected_ids(&self, id: NodeId) -> Option<&Vec<NodeId>> {
+        self.drones
+            .iter()
+            .map(|d| d as &dyn Node)
+            .chain(self.clients.iter().map(|c| c as &dyn Node))
+            .chain(self.servers.iter().map(|s| s as &dyn Node))
+            .find(|node| node.id() == id)
+            .map(Node::connected_drone_ids)
+    }
+
+    /// Returns the connections of the node `id`
+    pub(crate) fn connected_ids_mut(&mut self, id: NodeId) -> Option<&mut Vec<NodeId>> {
+        if let Some(drone) = self.drones.iter_mut().find(|d| d.id == id) {
+            return Some(&mut drone.connected_drone_ids);
+        }
+        if let Some(client) = self.clients.iter_mut().find(|c| c.id == id) {
+            return Some(&mut client.connected_drone_ids);
+        }
+        self.servers
+            .iter_mut()
+            .find(|s| s.id == id)
+            .map(|s| &mut s.connected_drone_ids)
+    }
+
+    /// Whether the link between `a` and `b` is cut by the partition, i.e. they are in different groups
+    #[must_use]
+    pub fn is_cut(&self, a: NodeId, b: NodeId) -> bool {
+        let Some(groups) = &self.partition else {
+            return false;
+        };
+        let group_of = |id: NodeId| groups.iter().position(|group| group.contains(&id));
+        matches!((group_of(a), group_of(b)), (Some(a), Some(b)) if a != b)
+    }
+
+    /// Returns every (client, server) pair where the client cannot reach the server.
+    /// Only drones forward packets and the links cut by the partition are not used
+    fn unreachable_pairs(&self) -> Vec<(NodeId, NodeId)> {
+        let connections: HashMap<NodeId, &Vec<NodeId>> = self
+            .drones
+            .iter()
+            .map(|d| (d.id, &d.connected_drone_ids))
+            .collect();
+        let mut unreachable = Vec::new();
+
+        for client in &self.clients {
+            let mut visited: HashSet<NodeId> = HashSet::from([client.id]);
+            let mut queue: VecDeque<NodeId> = VecDeque::new();
+
+            for id in &client.connected_drone_ids {
+                if !self.is_cut(client.id, *id) && visited.insert(*id) {
+                    queue.push_back(*id);
+                }
+            }
+
+            while let Some(id) = queue.pop_front() {
+                // Clients and servers are not expanded since they do not forward packets
+                let Some(neighbors) = connections.get(&id) else {
+                    continue;
+                };
+                for neighbor in *neighbors {
+                    if !self.is_cut(id, *neighbor) && visited.insert(*neighbor) {
+                        queue.push_back(*neighbor);
+                    }
+                }
+            }
+
+            unreachable.extend(
+                self.servers
+                    .iter()
+                    .filter(|s| !visited.contains(&s.id))
+                    .map(|s| (client.id, s.id)),
+            );
+        }
+
+        unreachable
+    }
+
+    /// Check that every client can reach every server, only drones forward packets
+    pub(crate) fn check_reachability(&self) -> Result<(), ConfigError> {
+        match self.unreachable_pairs().first() {
+            Some((client, server)) => Err(ConfigError::UnreachableServer(*client, *server)),
+            None => Ok(()),
+        }
+    }
+
+    /// Check that every client can still reach the servers it reaches in `previous`.
+    /// Without a partition it is the same as `check_reachability()`, with a partition the pairs
+    /// separated by the cut links are allowed
+    pub(crate) fn check_reachability_from(&self, previous: &Parser) -> Result<(), ConfigError> {
+        let previous = previous.unreachable_pairs();
+        match self
+            .unreachable_pairs()
+            .into_iter()
+            .find(|pair| !previous.contains(pair))
+        {
+            Some((client, server)) => Err(ConfigError::UnreachableServer(client, server)),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_topology(&self) -> Result<(), ConfigError> {
         let all_ids: HashSet<NodeId> = self
             .drones
             .iter()
@@ -106,6 +264,10 @@ impl Parser {
             return Err(ConfigError::DuplicatedNodeId);
         }
 
+        if self.channel_capacity == Some(0) {
+            return Err(ConfigError::ZeroChannelCapacity);
+        }
+
         // Check that clients are not connected to servers
         for client in &self.clients {
             for server in &self.servers {
@@ -131,6 +293,26 @@ impl Parser {
         Parser::generic_check_topology(&self.clients, &all_ids, &node_map)?;
         Parser::generic_check_topology(&self.servers, &all_ids, &node_map)?;
 
+        self.check_links(&node_map)
+    }
+
+    fn check_links(&self, node_map: &HashMap<NodeId, &dyn Node>) -> Result<(), ConfigError> {
+        for (i, link) in self.links.iter().enumerate() {
+            // Links must follow an existing connection and be described only once
+            let connected = node_map
+                .get(&link.from)
+                .is_some_and(|node| node.connected_drone_ids().contains(&link.to));
+            let duplicated = self.links[..i]
+                .iter()
+                .any(|other| other.connects(link.from, link.to));
+
+            let valid_loss = link.loss.is_none_or(|loss| loss.is_valid());
+
+            if !connected || duplicated || !valid_loss || link.bandwidth_kbps == Some(0) {
+                return Err(ConfigError::InvalidLink(link.from, link.to));
+            }
+        }
+
         Ok(())
     }
 }
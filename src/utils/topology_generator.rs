@@ -0,0 +1,152 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use wg_internal::network::NodeId;
+
+use super::errors::ConfigError;
+use super::parser::Parser;
+use crate::parsed_nodes::{ParsedClient, ParsedDrone, ParsedServer};
+
+/// Shape of a randomly generated topology
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopologyConfig {
+    pub drones: usize,
+    pub clients: usize,
+    pub servers: usize,
+    /// Links between drones added on top of the spanning tree connecting all of them
+    pub extra_links: usize,
+    /// Lowest and highest PDR of the drones
+    pub pdr_range: (f32, f32),
+}
+
+impl Default for TopologyConfig {
+    fn default() -> Self {
+        TopologyConfig {
+            drones: 10,
+            clients: 2,
+            servers: 2,
+            extra_links: 5,
+            pdr_range: (0.0, 0.1),
+        }
+    }
+}
+
+impl TopologyConfig {
+    fn is_valid(&self) -> bool {
+        let (min_pdr, max_pdr) = self.pdr_range;
+        let nodes = self.drones + self.clients + self.servers;
+
+        self.drones > 0
+            // Servers need two drones
+            && (self.servers == 0 || self.drones >= 2)
+            && nodes <= usize::from(NodeId::MAX)
+            && (0.0..=1.0).contains(&min_pdr)
+            && (min_pdr..=1.0).contains(&max_pdr)
+    }
+}
+
+fn node_id(index: usize) -> NodeId {
+    NodeId::try_from(index).expect("Node ids are checked by TopologyConfig::is_valid")
+}
+
+/// Connects `node` to `amount` distinct drones and returns them
+fn pick_drones(
+    drones: &mut [ParsedDrone],
+    node: NodeId,
+    amount: usize,
+    rng: &mut StdRng,
+) -> Vec<NodeId> {
+    let mut picked: Vec<NodeId> = drones
+        .choose_multiple(rng, amount)
+        .map(|drone| drone.id)
+        .collect();
+    picked.sort_unstable();
+
+    for drone in drones.iter_mut().filter(|d| picked.contains(&d.id)) {
+        drone.connected_drone_ids.push(node);
+    }
+    picked
+}
+
+/// Generate a topology from the seed, the same seed always gives the same topology.
+/// Drones take the ids from 1, followed by the clients and then the servers.
+/// The drones are connected by a random spanning tree plus `extra_links` random links,
+/// every client is connected to one or two drones and every server to two drones.
+/// The seed is also stored in the topology, so that it drives the rest of the simulation.
+/// # Errors
+/// Returns `ConfigError::InvalidTopologyConfig` if the topology cannot be built from the configuration,
+/// or the error of the topology checks
+pub fn generate_topology(config: &TopologyConfig, seed: u64) -> Result<Parser, ConfigError> {
+    if !config.is_valid() {
+        return Err(ConfigError::InvalidTopologyConfig);
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (min_pdr, max_pdr) = config.pdr_range;
+
+    let mut drones: Vec<ParsedDrone> = (1..=config.drones)
+        .map(|i| ParsedDrone {
+            id: node_id(i),
+            connected_drone_ids: Vec::new(),
+            pdr: rng.gen_range(min_pdr..=max_pdr),
+            channel_capacity: None,
+        })
+        .collect();
+
+    // Every drone joins the tree through one of the drones before it
+    let mut edges: Vec<(usize, usize)> = (1..drones.len())
+        .map(|i| (rng.gen_range(0..i), i))
+        .collect();
+
+    let mut candidates: Vec<(usize, usize)> = (0..drones.len())
+        .flat_map(|i| (i + 1..drones.len()).map(move |j| (i, j)))
+        .filter(|edge| !edges.contains(edge))
+        .collect();
+    candidates.shuffle(&mut rng);
+    edges.extend(candidates.into_iter().take(config.extra_links));
+
+    for (i, j) in edges {
+        let (a, b) = (drones[i].id, drones[j].id);
+        drones[i].connected_drone_ids.push(b);
+        drones[j].connected_drone_ids.push(a);
+    }
+
+    let clients: Vec<ParsedClient> = (0..config.clients)
+        .map(|i| {
+            let id = node_id(config.drones + 1 + i);
+            let amount = rng.gen_range(1..=2.min(config.drones));
+            ParsedClient {
+                id,
+                connected_drone_ids: pick_drones(&mut drones, id, amount, &mut rng),
+                channel_capacity: None,
+                log_level: None,
+            }
+        })
+        .collect();
+
+    let servers: Vec<ParsedServer> = (0..config.servers)
+        .map(|i| {
+            let id = node_id(config.drones + config.clients + 1 + i);
+            ParsedServer {
+                id,
+                connected_drone_ids: pick_drones(&mut drones, id, 2, &mut rng),
+                channel_capacity: None,
+                log_level: None,
+            }
+        })
+        .collect();
+
+    for drone in &mut drones {
+        drone.connected_drone_ids.sort_unstable();
+    }
+
+    let mut parser = Parser::new(None)?;
+    parser.drones = drones;
+    parser.clients = clients;
+    parser.servers = servers;
+    parser.seed = Some(seed);
+
+    parser.check_topology()?;
+    parser.check_reachability()?;
+    Ok(parser)
+}
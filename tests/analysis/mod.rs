@@ -0,0 +1,5 @@
+pub mod test_lint;
+pub mod test_reliability;
+pub mod test_routes;
+pub mod test_topology;
+pub mod test_traffic;
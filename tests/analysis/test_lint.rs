@@ -0,0 +1,64 @@
+mod analysis {
+    use network_initializer::analysis::lint::{lint_topology, LintPolicy, TopologyWarning};
+    use network_initializer::parser::Parser;
+    use network_initializer::NetworkInitializer;
+
+    #[test]
+    fn test_articulation_drone() {
+        let parser = Parser::new(Some(
+            "initialization_files/test_files/topology/diamond.toml",
+        ));
+        let warnings = lint_topology(&parser.unwrap());
+
+        assert_eq!(
+            warnings,
+            vec![TopologyWarning::ArticulationDrone {
+                drone: 3,
+                affected: vec![(10, 20)],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_bridge_link() {
+        let parser = Parser::new(Some("initialization_files/test_files/topology/bridge.toml"));
+        let warnings = lint_topology(&parser.unwrap());
+
+        assert_eq!(
+            warnings,
+            vec![
+                TopologyWarning::ArticulationDrone {
+                    drone: 1,
+                    affected: vec![(10, 20)],
+                },
+                TopologyWarning::BridgeLink {
+                    a: 1,
+                    b: 10,
+                    affected: vec![(10, 20)],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_warnings() {
+        let parser = Parser::new(Some("initialization_files/test_files/links/ok.toml"));
+        assert_eq!(lint_topology(&parser.unwrap()), vec![]);
+    }
+
+    #[test]
+    fn test_denied_launch_can_be_retried() {
+        let path = "initialization_files/test_files/topology/bridge.toml";
+        let mut net_init = NetworkInitializer::new(Some(path)).unwrap();
+        net_init.set_lint_policy(LintPolicy::Deny);
+        let _ = net_init.get_controller_recv();
+        let _ = net_init.get_controller_senders();
+        let _ = net_init.get_channels();
+
+        // The initializer is still ready after a refused launch, the second one fails on the lints again
+        for _ in 0..2 {
+            let err = net_init.run_simulation(None, None).unwrap_err();
+            assert!(err.starts_with("Topology warnings"), "{err}");
+        }
+    }
+}
@@ -0,0 +1,19 @@
+mod analysis {
+    use network_initializer::analysis::reliability::estimate_reliability;
+    use network_initializer::parser::Parser;
+
+    const TOPOLOGY: &str = "initialization_files/test_files/topology/diamond.toml";
+
+    #[test]
+    fn test_best_path() {
+        let report = estimate_reliability(&Parser::new(Some(TOPOLOGY)).unwrap());
+        let pair = report
+            .pair(10, 20)
+            .expect("Pair 10 -> 20 must be estimated");
+
+        // Drone 1 drops less than drone 2, drone 3 never drops and drone 4 drops half of the fragments
+        assert_eq!(pair.best_path, vec![10, 1, 3, 20]);
+        assert!((pair.delivery_probability - 0.9).abs() < 1e-6);
+        assert!((pair.expected_transmissions.unwrap() - 1.0 / 0.9).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,44 @@
+mod analysis {
+    use network_initializer::analysis::routes::{reference_routes, RouteMetric};
+    use network_initializer::parser::Parser;
+
+    const TOPOLOGY: &str = "initialization_files/test_files/topology/diamond.toml";
+
+    #[test]
+    fn test_hop_count_routes() {
+        let parser = Parser::new(Some(TOPOLOGY)).unwrap();
+        let table = reference_routes(&parser, RouteMetric::HopCount);
+
+        assert_eq!(table.routes.len(), 1);
+        assert_eq!(table.route(10, 20), Some(&vec![10, 1, 3, 20]));
+
+        let header = table.header(10, 20).unwrap();
+        assert_eq!(header.hop_index, 1);
+        assert_eq!(header.hops, vec![10, 1, 3, 20]);
+    }
+
+    #[test]
+    fn test_pdr_routes() {
+        let mut parser = Parser::new(Some(TOPOLOGY)).unwrap();
+        // Make drone 1 the worst one, so that the route goes through drone 2
+        parser.drones[0].pdr = 0.9;
+
+        let table = reference_routes(&parser, RouteMetric::Pdr);
+        assert_eq!(table.route(10, 20), Some(&vec![10, 2, 3, 20]));
+
+        let table = reference_routes(&parser, RouteMetric::HopCount);
+        assert_eq!(table.route(10, 20), Some(&vec![10, 1, 3, 20]));
+    }
+
+    #[test]
+    fn test_tie_lowest_path() {
+        // Both routes have two drones with the same PDR
+        let path = "initialization_files/test_files/topology/tie.toml";
+        let parser = Parser::new(Some(path)).unwrap();
+
+        for metric in [RouteMetric::HopCount, RouteMetric::Pdr] {
+            let table = reference_routes(&parser, metric);
+            assert_eq!(table.route(10, 20), Some(&vec![10, 1, 5, 20]));
+        }
+    }
+}
@@ -0,0 +1,27 @@
+mod analysis {
+    use std::collections::BTreeMap;
+
+    use network_initializer::analysis::topology::analyze_topology;
+    use network_initializer::parser::Parser;
+
+    const TOPOLOGY: &str = "initialization_files/test_files/topology/diamond.toml";
+
+    #[test]
+    fn test_topology_metrics() {
+        let parser = Parser::new(Some(TOPOLOGY)).unwrap();
+        let report = analyze_topology(&parser);
+
+        assert_eq!(
+            report.degrees,
+            BTreeMap::from([(1, 2), (2, 2), (3, 4), (4, 2), (10, 2), (20, 2)])
+        );
+        assert_eq!(report.diameter, Some(3));
+        assert_eq!(report.average_client_server_path, Some(3.0));
+        assert_eq!(report.edge_disjoint_paths, BTreeMap::from([((10, 20), 2)]));
+        assert_eq!(report.articulation_points, vec![3]);
+        assert_eq!(
+            report.betweenness,
+            BTreeMap::from([(1, 1.5), (2, 1.5), (3, 7.0), (4, 0.0)])
+        );
+    }
+}
@@ -0,0 +1,65 @@
+mod analysis {
+    use network_initializer::analysis::traffic::{analyze_traffic, DropLocation};
+    use network_initializer::capture::{read_capture, CapturedNackType};
+
+    const CAPTURE: &str = "initialization_files/test_files/capture/session.jsonl";
+
+    #[test]
+    fn test_session_flow() {
+        let report = analyze_traffic(&read_capture(CAPTURE).unwrap());
+        let session = report
+            .session(20, 42)
+            .expect("Session 42 must be reconstructed");
+
+        assert_eq!((session.source, session.destination), (20, 30));
+        assert_eq!(session.total_n_fragments, 2);
+        assert_eq!(session.delivered_fragments(), 1);
+        assert!((session.delivery_ratio() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(session.retransmissions(), 1);
+        assert_eq!(session.acks, 1);
+        assert_eq!(session.nacks.len(), 1);
+
+        let delivered = &session.fragments[&0];
+        assert!(delivered.delivered);
+        assert_eq!(delivered.hop_count, Some(3));
+
+        let dropped = &session.fragments[&1];
+        assert!(!dropped.delivered);
+        assert_eq!(dropped.transmissions, 2);
+        assert_eq!(
+            dropped.drops,
+            vec![DropLocation::Drone(1), DropLocation::Link(1, 2)]
+        );
+    }
+
+    #[test]
+    fn test_drop_counters() {
+        let report = analyze_traffic(&read_capture(CAPTURE).unwrap());
+
+        assert_eq!(report.drone_drops.get(&1), Some(&1));
+        assert_eq!(report.link_losses.get(&(1, 2)), Some(&1));
+        assert_eq!(report.floods.len(), 1);
+        assert_eq!(report.flood_requests, 1);
+        assert!(report.sessions_with_id(8).next().is_none());
+    }
+
+    #[test]
+    fn test_shared_session_id() {
+        // Clients 20 and 21 both send their session 5 to server 30
+        let path = "initialization_files/test_files/capture/shared_session.jsonl";
+        let report = analyze_traffic(&read_capture(path).unwrap());
+        assert_eq!(report.sessions.len(), 2);
+        assert_eq!(report.sessions_with_id(5).count(), 2);
+
+        let delivered = report.session(20, 5).unwrap();
+        assert_eq!(delivered.delivered_fragments(), 1);
+        assert_eq!(delivered.acks, 1);
+        assert!(delivered.nacks.is_empty());
+
+        let dropped = report.session(21, 5).unwrap();
+        assert_eq!(dropped.delivered_fragments(), 0);
+        assert_eq!(dropped.acks, 0);
+        assert_eq!(dropped.nacks, vec![(0, CapturedNackType::Dropped)]);
+        assert_eq!(dropped.fragments[&0].drops, vec![DropLocation::Drone(1)]);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod test_check;
+pub mod test_content_ids;
+pub mod test_generator;
@@ -0,0 +1,66 @@
+mod assets {
+    use network_initializer::assets::check::check_asset_dir;
+    use network_initializer::assets::AssetKind;
+    use network_initializer::errors::AssetError;
+
+    const TEST_FILES: &str = "initialization_files/test_files/assets";
+
+    #[test]
+    fn test_shipped_assets() {
+        for i in 1..=3 {
+            let path = format!("initialization_files/client_audio/client{i}");
+            assert_eq!(check_asset_dir(&path, AssetKind::ClientAudio), vec![]);
+        }
+        assert_eq!(
+            check_asset_dir(
+                "initialization_files/client_video/client1",
+                AssetKind::ClientVideo
+            ),
+            vec![]
+        );
+        for i in 1..=2 {
+            let path = format!("initialization_files/server/server{i}");
+            assert_eq!(check_asset_dir(&path, AssetKind::Server), vec![]);
+        }
+    }
+
+    #[test]
+    fn test_missing_content() {
+        let path = format!("{TEST_FILES}/client_audio_broken");
+        let errors = check_asset_dir(&path, AssetKind::ClientAudio);
+
+        assert_eq!(
+            errors,
+            vec![
+                AssetError::MissingSegment {
+                    playlist: format!("{path}/songs/tokyocafe/playlist.m3u8"),
+                    segment: "segment1.ts".to_string(),
+                },
+                AssetError::MissingContent {
+                    path: format!("{path}/songs/missingsong"),
+                    title: "Missing Song".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_metadata() {
+        let path = format!("{TEST_FILES}/invalid_metadata");
+        assert_eq!(
+            check_asset_dir(&path, AssetKind::ClientAudio),
+            vec![AssetError::InvalidMetadata(format!(
+                "{path}/songs_metadata.json"
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_missing_directory() {
+        let path = format!("{TEST_FILES}/does_not_exist");
+        assert_eq!(
+            check_asset_dir(&path, AssetKind::Server),
+            vec![AssetError::MissingDirectory(path)]
+        );
+    }
+}
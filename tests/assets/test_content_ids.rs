@@ -0,0 +1,138 @@
+mod assets {
+    use std::collections::HashSet;
+    use std::fs;
+
+    use network_initializer::assets::content_ids::{
+        assign_content_ids, find_duplicate_ids, find_duplicate_titles,
+    };
+    use network_initializer::assets::generator::{generate_assets, AssetPackConfig};
+    use network_initializer::assets::metadata::{SongList, VideoList};
+    use network_initializer::assets::{AssetKind, AssetLayout};
+    use network_initializer::errors::AssetError;
+    use network_initializer::manifest::{NodeManifest, NodeManifestEntry};
+    use network_initializer::parsed_nodes::NodeType;
+
+    fn read_ids(layout: &AssetLayout) -> Vec<u16> {
+        let mut ids = Vec::new();
+        for i in 0..layout.servers {
+            let dir = layout.path(AssetKind::Server, i);
+            let songs: SongList = serde_json::from_str(
+                &fs::read_to_string(format!("{dir}/init_songs.json")).unwrap(),
+            )
+            .unwrap();
+            let videos: VideoList = serde_json::from_str(
+                &fs::read_to_string(format!("{dir}/init_videos.json")).unwrap(),
+            )
+            .unwrap();
+            ids.extend(songs.songs.iter().map(|song| song.id));
+            ids.extend(videos.videos.iter().map(|video| video.id));
+        }
+        ids
+    }
+
+    #[test]
+    fn test_unique_ids() {
+        let root = std::env::temp_dir().join(format!("content_ids_{}", std::process::id()));
+        let root = root.to_str().unwrap();
+        let config = AssetPackConfig {
+            segment_size: 16,
+            video_size: 16,
+            ..AssetPackConfig::default()
+        };
+
+        let layout = generate_assets(root, &config).unwrap();
+        let ids = read_ids(&layout);
+        let unique: HashSet<_> = ids.iter().collect();
+
+        assert_eq!(unique.len(), ids.len());
+        assert!(!ids.contains(&0));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_only_ids_rewritten() {
+        let fixture = "initialization_files/test_files/assets/formatted/video_metadata.json";
+        let root = std::env::temp_dir().join(format!("content_ids_format_{}", std::process::id()));
+        let dir = root.join("client_video").join("client1");
+        fs::create_dir_all(&dir).unwrap();
+        fs::copy(fixture, dir.join("video_metadata.json")).unwrap();
+
+        let layout = AssetLayout {
+            root: root.to_str().unwrap().to_string(),
+            client_audio: 0,
+            client_video: 1,
+            servers: 0,
+        };
+        assign_content_ids(&layout).unwrap();
+
+        // Indentation, field order, unknown fields and the final newline are kept
+        let expected = fs::read_to_string(fixture)
+            .unwrap()
+            .replacen("\"id\": 0", "\"id\": 1", 1)
+            .replacen("\"id\": 7", "\"id\": 2", 1);
+        assert_eq!(
+            fs::read_to_string(dir.join("video_metadata.json")).unwrap(),
+            expected
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    /// Two servers cycling onto the same folder
+    fn shared_folder() -> NodeManifest {
+        let server = |id, folder: &str| NodeManifestEntry {
+            id,
+            kind: NodeType::Server,
+            implementation: "Server".to_string(),
+            pdr: None,
+            neighbours: Vec::new(),
+            asset_path: Some(format!("initialization_files/server/{folder}")),
+        };
+        NodeManifest {
+            seed: 0,
+            nodes: vec![server(30, "server2"), server(31, "server2")],
+            partition: None,
+        }
+    }
+
+    #[test]
+    fn test_duplicate_titles() {
+        let manifest = shared_folder();
+
+        assert_eq!(
+            find_duplicate_titles(&manifest),
+            vec![
+                AssetError::DuplicateTitle {
+                    title: "never-forget-my-name".to_string(),
+                    first: 30,
+                    second: 31,
+                },
+                AssetError::DuplicateTitle {
+                    title: "Kid on crack".to_string(),
+                    first: 30,
+                    second: 31,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_ids() {
+        assert_eq!(
+            find_duplicate_ids(&shared_folder()),
+            vec![
+                AssetError::DuplicateContentId {
+                    id: 14,
+                    first: 30,
+                    second: 31,
+                },
+                AssetError::DuplicateContentId {
+                    id: 15,
+                    first: 30,
+                    second: 31,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,41 @@
+mod assets {
+    use std::fs;
+
+    use network_initializer::assets::check::check_asset_dir;
+    use network_initializer::assets::generator::{generate_assets, AssetPackConfig};
+    use network_initializer::assets::AssetKind;
+
+    #[test]
+    fn test_generated_assets_are_valid() {
+        let root = std::env::temp_dir().join(format!("generated_assets_{}", std::process::id()));
+        let root = root.to_str().unwrap();
+        let config = AssetPackConfig {
+            client_audio: 4,
+            client_video: 2,
+            servers: 3,
+            segment_size: 128,
+            video_size: 256,
+            ..AssetPackConfig::default()
+        };
+
+        let layout = generate_assets(root, &config).unwrap();
+
+        for (kind, count) in [
+            (AssetKind::ClientAudio, 4),
+            (AssetKind::ClientVideo, 2),
+            (AssetKind::Server, 3),
+        ] {
+            for i in 0..count {
+                assert_eq!(check_asset_dir(&layout.path(kind, i), kind), vec![]);
+            }
+        }
+
+        let segment = format!(
+            "{}/songs/server1song1/segment2.ts",
+            layout.path(AssetKind::Server, 0)
+        );
+        assert_eq!(fs::metadata(segment).unwrap().len(), 128);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,90 @@
+mod capture {
+    use network_initializer::capture::{
+        read_capture, CapturedNackType, CapturedPacket, CapturedPacketType,
+    };
+    use network_initializer::errors::CaptureError;
+    use wg_internal::network::SourceRoutingHeader;
+    use wg_internal::packet::{Fragment, Packet, PacketType, FRAGMENT_DSIZE};
+
+    #[test]
+    fn test_read_ok() {
+        let path = "initialization_files/test_files/capture/session.jsonl";
+        let packets = read_capture(path);
+
+        assert!(packets.is_ok(), "{}", packets.err().unwrap());
+
+        let packets = packets.unwrap();
+        assert_eq!(packets.len(), 10);
+        assert_eq!(packets[0].session_id, 42);
+        assert_eq!(packets[0].hops, vec![20, 1, 2, 30]);
+        assert!(packets[6].lost_on_link);
+        assert_eq!(
+            packets[9].pack_type,
+            CapturedPacketType::Nack {
+                fragment_index: 0,
+                nack_type: CapturedNackType::ErrorInRouting(5),
+            }
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let path = "initialization_files/test_files/capture/session.jsonl";
+
+        for captured in read_capture(path).unwrap() {
+            let packet = captured.to_packet();
+            let mut recaptured = CapturedPacket::new(captured.from, captured.to, &packet);
+            recaptured.timestamp_us = captured.timestamp_us;
+            recaptured.lost_on_link = captured.lost_on_link;
+
+            assert_eq!(recaptured, captured);
+        }
+    }
+
+    #[test]
+    fn test_oversized_fragment_length() {
+        let packet = Packet {
+            routing_header: SourceRoutingHeader {
+                hop_index: 1,
+                hops: vec![20, 1, 30],
+            },
+            session_id: 7,
+            pack_type: PacketType::MsgFragment(Fragment {
+                fragment_index: 0,
+                total_n_fragments: 1,
+                length: u8::MAX,
+                data: [1; FRAGMENT_DSIZE],
+            }),
+        };
+
+        let captured = CapturedPacket::new(20, 1, &packet);
+        assert_eq!(
+            captured.pack_type,
+            CapturedPacketType::MsgFragment {
+                fragment_index: 0,
+                total_n_fragments: 1,
+                data: vec![1; FRAGMENT_DSIZE],
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_record() {
+        let path = "initialization_files/test_files/capture/invalid.jsonl";
+
+        assert_eq!(
+            read_capture(path).err().unwrap(),
+            CaptureError::InvalidRecord(1)
+        );
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let path = "initialization_files/test_files/capture/missing.jsonl";
+
+        assert_eq!(
+            read_capture(path).err().unwrap(),
+            CaptureError::FileError(path.to_string())
+        );
+    }
+}
@@ -0,0 +1,127 @@
+mod chaos {
+    use std::time::Duration;
+
+    use network_initializer::analysis::routes::{reference_routes, RouteMetric};
+    use network_initializer::errors::ChaosError;
+    use network_initializer::simulation::scenario::ScenarioAction;
+    use network_initializer::simulation::{ChaosConfig, ChaosEngine, SimulationController};
+    use network_initializer::topology_generator::TopologyConfig;
+    use network_initializer::NetworkInitializer;
+
+    const SEED: u64 = 11;
+
+    /// The initializer is returned too, it holds the command receivers of the nodes
+    fn controller() -> (NetworkInitializer, SimulationController) {
+        let mut net_init = NetworkInitializer::generate(&TopologyConfig::default(), SEED).unwrap();
+        let controller = SimulationController::new(&mut net_init);
+        (net_init, controller)
+    }
+
+    fn config() -> ChaosConfig {
+        ChaosConfig {
+            interval: Duration::ZERO,
+            actions: 50,
+            ..ChaosConfig::default()
+        }
+    }
+
+    fn run(controller: &SimulationController) -> Vec<ScenarioAction> {
+        ChaosEngine::new(controller, config())
+            .unwrap()
+            .run()
+            .into_iter()
+            .map(|step| step.action)
+            .collect()
+    }
+
+    #[test]
+    fn test_controller_seed() {
+        assert_eq!(controller().1.seed(), SEED);
+    }
+
+    #[test]
+    fn test_same_seed_same_actions() {
+        let (_net_init, first) = controller();
+        let actions = run(&first);
+
+        assert!(actions
+            .iter()
+            .any(|action| matches!(action, ScenarioAction::Crash { .. })));
+        assert!(actions.iter().any(|action| matches!(
+            action,
+            ScenarioAction::AddLink { .. } | ScenarioAction::RemoveLink { .. }
+        )));
+
+        let (_net_init, second) = controller();
+        assert_eq!(actions, run(&second));
+    }
+
+    #[test]
+    fn test_servers_stay_connected() {
+        let (_net_init, controller) = controller();
+        let actions = run(&controller);
+        assert!(!actions.is_empty());
+
+        let topology = controller.topology();
+        for server in &topology.servers {
+            assert!(server.connected_drone_ids.len() >= 2);
+        }
+        let routes = reference_routes(&topology, RouteMetric::HopCount);
+        assert_eq!(
+            routes.routes.len(),
+            topology.clients.len() * topology.servers.len()
+        );
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let (_net_init, controller) = controller();
+        let check = |config: ChaosConfig| ChaosEngine::new(&controller, config).err();
+
+        assert_eq!(
+            check(ChaosConfig {
+                pdr_range: 0.5..=0.2,
+                ..config()
+            }),
+            Some(ChaosError::InvalidPdrRange(0.5, 0.2))
+        );
+        assert_eq!(
+            check(ChaosConfig {
+                pdr_range: 0.0..=1.5,
+                ..config()
+            }),
+            Some(ChaosError::InvalidPdrRange(0.0, 1.5))
+        );
+        assert_eq!(
+            check(ChaosConfig {
+                min_server_degree: 1,
+                ..config()
+            }),
+            Some(ChaosError::InvalidServerDegree(1))
+        );
+        assert_eq!(
+            check(ChaosConfig {
+                actions: 0,
+                ..config()
+            }),
+            Some(ChaosError::InvalidActions(0))
+        );
+        assert_eq!(
+            check(ChaosConfig {
+                crash_weight: 0,
+                pdr_weight: 0,
+                link_weight: 0,
+                ..config()
+            }),
+            Some(ChaosError::InvalidWeights)
+        );
+        assert_eq!(
+            check(ChaosConfig {
+                crash_weight: u32::MAX,
+                ..config()
+            }),
+            Some(ChaosError::InvalidWeights)
+        );
+        assert!(check(config()).is_none());
+    }
+}
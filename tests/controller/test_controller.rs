@@ -0,0 +1,224 @@
+mod controller {
+    use std::collections::HashMap;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crossbeam::channel::{Receiver, Sender};
+    use network_initializer::errors::{ConfigError, ControllerError};
+    use network_initializer::event_log::ReplayTiming;
+    use network_initializer::simulation::SimulationController;
+    use network_initializer::simulation_event::ControllerEvent;
+    use network_initializer::{DroneType, NetworkInitializer, RestartPolicy};
+    use wg_internal::controller::{DroneCommand, DroneEvent};
+    use wg_internal::drone::Drone;
+    use wg_internal::network::NodeId;
+    use wg_internal::packet::{Packet, PacketType};
+
+    const TOPOLOGY: &str = "initialization_files/test_files/topology/diamond.toml";
+    const EVENTS: &str = "initialization_files/test_files/event_log/events.jsonl";
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    /// Drone panicking as soon as it runs, which leaves it unreachable
+    struct PanickingDrone;
+
+    impl Drone for PanickingDrone {
+        fn new(
+            _id: NodeId,
+            _controller_send: Sender<DroneEvent>,
+            _controller_recv: Receiver<DroneCommand>,
+            _packet_recv: Receiver<Packet>,
+            _packet_send: HashMap<NodeId, Sender<Packet>>,
+            _pdr: f32,
+        ) -> Self {
+            PanickingDrone
+        }
+
+        fn run(&mut self) {
+            panic!("unreachable drone");
+        }
+    }
+
+    #[test]
+    fn test_deliver_shortcut() {
+        let mut net_init = NetworkInitializer::new(Some(TOPOLOGY)).unwrap();
+        let channels = net_init.get_channels();
+        let controller = SimulationController::new(&mut net_init);
+        let events = controller.events();
+        let _ = controller.spawn();
+
+        net_init
+            .replay_events(EVENTS, ReplayTiming::Immediate)
+            .unwrap();
+
+        // The shortcut is a nack routed to the server 20
+        let packet = channels[&20].receiver.recv_timeout(TIMEOUT).unwrap();
+        assert_eq!(packet.session_id, 42);
+        assert!(matches!(packet.pack_type, PacketType::Nack(_)));
+
+        // Every event is still forwarded, the shortcut included
+        let forwarded: Vec<ControllerEvent> = (0..3)
+            .map(|_| events.recv_timeout(TIMEOUT).unwrap())
+            .collect();
+        assert!(matches!(
+            forwarded[0],
+            ControllerEvent::Node(DroneEvent::PacketSent(_))
+        ));
+        assert!(matches!(
+            forwarded[2],
+            ControllerEvent::Node(DroneEvent::ControllerShortcut(_))
+        ));
+        assert!(channels[&10].receiver.is_empty());
+    }
+
+    #[test]
+    fn test_commands() {
+        let mut net_init = NetworkInitializer::new(Some(TOPOLOGY)).unwrap();
+        let controller = SimulationController::new(&mut net_init);
+
+        assert_eq!(controller.set_packet_drop_rate(1, 0.3), Ok(()));
+        assert_eq!(
+            controller.set_packet_drop_rate(1, 1.5),
+            Err(ControllerError::InvalidPdr(1.5))
+        );
+        assert_eq!(
+            controller.set_packet_drop_rate(10, 0.3),
+            Err(ControllerError::NotADrone(10))
+        );
+        assert_eq!(controller.add_sender(1, 2), Ok(()));
+        assert_eq!(
+            controller.add_sender(1, 99),
+            Err(ControllerError::NodeNotFound(99))
+        );
+        assert_eq!(controller.remove_sender(1, 2), Ok(()));
+        assert_eq!(controller.crash(99), Err(ControllerError::NodeNotFound(99)));
+    }
+
+    #[test]
+    fn test_link_validation() {
+        let mut net_init = NetworkInitializer::new(Some(TOPOLOGY)).unwrap();
+        let controller = SimulationController::new(&mut net_init);
+
+        assert_eq!(
+            controller.add_link(1, 3),
+            Err(ControllerError::AlreadyConnected(1, 3))
+        );
+        assert_eq!(
+            controller.add_link(1, 99),
+            Err(ControllerError::NodeNotFound(99))
+        );
+        assert_eq!(
+            controller.remove_link(1, 2),
+            Err(ControllerError::NotConnected(1, 2))
+        );
+        assert_eq!(
+            controller.add_link(10, 20),
+            Err(ControllerError::InvalidTopology(
+                ConfigError::ClientConnectedToServer(10, 20)
+            ))
+        );
+        assert_eq!(
+            controller.remove_link(3, 20),
+            Err(ControllerError::InvalidTopology(
+                ConfigError::ServerWithLessThanTwoConnections(20)
+            ))
+        );
+
+        // The client keeps a single path, removing it would disconnect the network
+        assert_eq!(controller.remove_link(10, 1), Ok(()));
+        assert_eq!(
+            controller.remove_link(10, 2),
+            Err(ControllerError::InvalidTopology(
+                ConfigError::UnreachableServer(10, 20)
+            ))
+        );
+        assert_eq!(controller.add_link(1, 2), Ok(()));
+    }
+
+    #[test]
+    fn test_partition_and_heal() {
+        let mut net_init = NetworkInitializer::new(Some(TOPOLOGY)).unwrap();
+        let controller = SimulationController::new(&mut net_init);
+
+        assert_eq!(
+            controller.partition(&[vec![1, 2]]),
+            Err(ControllerError::InvalidPartition)
+        );
+        assert_eq!(
+            controller.partition(&[vec![1, 2], vec![2, 3]]),
+            Err(ControllerError::InvalidPartition)
+        );
+        assert_eq!(
+            controller.partition(&[vec![1, 2], vec![]]),
+            Err(ControllerError::InvalidPartition)
+        );
+        assert_eq!(
+            controller.partition(&[vec![1], vec![99]]),
+            Err(ControllerError::NodeNotFound(99))
+        );
+        assert_eq!(controller.heal(), Err(ControllerError::NotPartitioned));
+
+        // The client keeps a single path to the server, through the drone 2
+        assert_eq!(
+            controller.partition(&[vec![1], vec![2, 3, 4, 10, 20]]),
+            Ok(())
+        );
+        assert_eq!(
+            controller.partition(&[vec![1], vec![2]]),
+            Err(ControllerError::AlreadyPartitioned)
+        );
+
+        let topology = controller.topology();
+        assert!(topology.is_cut(1, 3));
+        assert!(topology.is_cut(10, 1));
+        assert!(!topology.is_cut(10, 2));
+        assert_eq!(topology.drones[0].connected_drone_ids, vec![10, 3]);
+
+        // Cut links do not count as paths
+        assert_eq!(
+            controller.remove_link(10, 2),
+            Err(ControllerError::InvalidTopology(
+                ConfigError::UnreachableServer(10, 20)
+            ))
+        );
+        // A link between the groups is cut as soon as it is added
+        assert_eq!(controller.add_link(1, 2), Ok(()));
+        assert!(controller.topology().is_cut(1, 2));
+
+        assert_eq!(controller.heal(), Ok(()));
+        assert_eq!(controller.heal(), Err(ControllerError::NotPartitioned));
+
+        let topology = controller.topology();
+        assert_eq!(topology.partition, None);
+        assert!(!topology.is_cut(1, 3));
+        assert_eq!(controller.remove_link(10, 2), Ok(()));
+    }
+
+    #[test]
+    fn test_crash_with_unreachable_neighbour() {
+        let mut net_init = NetworkInitializer::new(Some(TOPOLOGY)).unwrap();
+        net_init.register_drone::<PanickingDrone>("panicking");
+        net_init.set_restart_policy(RestartPolicy::Never);
+        let controller = SimulationController::new(&mut net_init);
+
+        assert_eq!(
+            controller.spawn_drone(5, DroneType::Custom("panicking"), 0.0, &[1, 2]),
+            Ok(())
+        );
+        // The channels of the drone are dropped once its thread is gone
+        let start = Instant::now();
+        while controller.crash(5) != Err(ControllerError::NodeUnreachable(5)) {
+            assert!(start.elapsed() < TIMEOUT, "drone 5 is still reachable");
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        // The drone 1 is removed from 10 and 3 before reaching 5, they get it back
+        assert_eq!(
+            controller.crash_node(1),
+            Err(ControllerError::NodeUnreachable(5))
+        );
+        let topology = controller.topology();
+        assert_eq!(topology.drones[0].connected_drone_ids, vec![10, 3, 5]);
+        assert_eq!(topology.drones[2].connected_drone_ids, vec![1, 2, 4, 20]);
+        assert_eq!(topology.clients[0].connected_drone_ids, vec![1, 2]);
+    }
+}
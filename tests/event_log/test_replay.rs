@@ -0,0 +1,29 @@
+mod event_log {
+    use crossbeam::channel::unbounded;
+    use network_initializer::event_log::{read_events, replay_events, RecordedEvent, ReplayTiming};
+    use wg_internal::controller::DroneEvent;
+
+    const EVENTS: &str = "initialization_files/test_files/event_log/events.jsonl";
+
+    #[test]
+    fn test_replay_order() {
+        let (send, recv) = unbounded();
+        replay_events(EVENTS, send, ReplayTiming::Immediate).unwrap();
+
+        let events: Vec<DroneEvent> = recv.iter().collect();
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], DroneEvent::PacketSent(_)));
+        assert!(matches!(events[1], DroneEvent::PacketDropped(_)));
+        assert!(matches!(events[2], DroneEvent::ControllerShortcut(_)));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for recorded in read_events(EVENTS).unwrap() {
+            let mut rebuilt = RecordedEvent::new(&recorded.to_event());
+            rebuilt.timestamp_us = recorded.timestamp_us;
+
+            assert_eq!(rebuilt, recorded);
+        }
+    }
+}
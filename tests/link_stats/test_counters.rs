@@ -0,0 +1,80 @@
+mod link_stats {
+    use std::time::Duration;
+
+    use network_initializer::capture::read_capture;
+    use network_initializer::simulation_event::{LinkDropCause, SimulationEvent};
+    use network_initializer::NetworkInitializer;
+    use wg_internal::packet::Packet;
+
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    fn captured_packets() -> Vec<Packet> {
+        read_capture("initialization_files/test_files/capture/session.jsonl")
+            .unwrap()
+            .iter()
+            .map(|captured| captured.to_packet())
+            .collect()
+    }
+
+    #[test]
+    fn test_counters() {
+        let path = "initialization_files/test_files/topology/diamond.toml";
+        let mut net_init = NetworkInitializer::new(Some(path)).unwrap();
+        net_init.enable_link_stats();
+
+        let channels = net_init.get_channels();
+        let sender = net_init.get_link_sender(10, 1).unwrap();
+        for packet in captured_packets() {
+            sender.send(packet).unwrap();
+        }
+        for _ in 0..10 {
+            channels[&1].receiver.recv_timeout(TIMEOUT).unwrap();
+        }
+
+        let stats = net_init.link_stats();
+        assert_eq!(stats.len(), 1);
+        let stats = &stats[0];
+        assert_eq!((stats.from, stats.to), (10, 1));
+        assert_eq!(stats.total_packets(), 10);
+        assert_eq!(stats.msg_fragments, 6);
+        assert_eq!(stats.acks, 1);
+        assert_eq!(stats.nacks, 2);
+        assert_eq!(stats.flood_requests, 1);
+        assert_eq!(stats.flood_responses, 0);
+        assert_eq!(stats.fragment_bytes, 15);
+        assert_eq!(stats.dropped, 0);
+    }
+
+    #[test]
+    fn test_overflow_drops() {
+        // Drone 1 holds at most 16 packets and the overflow policy is drop
+        let path = "initialization_files/test_files/channel_capacity/ok.toml";
+        let mut net_init = NetworkInitializer::new(Some(path)).unwrap();
+        net_init.enable_link_stats();
+        let events = net_init.get_simulation_recv();
+
+        let sender = net_init.get_link_sender(5, 1).unwrap();
+        let packet = captured_packets().remove(0);
+        for _ in 0..20 {
+            sender.send(packet.clone()).unwrap();
+        }
+
+        for _ in 0..4 {
+            let event = events.recv_timeout(TIMEOUT).unwrap();
+            assert_eq!(
+                event,
+                SimulationEvent::LinkDropped {
+                    from: 5,
+                    to: 1,
+                    session_id: 42,
+                    cause: LinkDropCause::Overflow,
+                }
+            );
+        }
+
+        let stats = &net_init.link_stats()[0];
+        assert_eq!(stats.msg_fragments, 20);
+        assert_eq!(stats.dropped, 4);
+        assert_eq!(stats.peak_queue_len, 16);
+    }
+}
@@ -1 +1,12 @@
+mod analysis;
+mod assets;
+mod capture;
+mod chaos;
+mod controller;
+mod event_log;
+mod link_stats;
+mod manifest;
 mod parser;
+mod scenario;
+mod supervision;
+mod topology_generator;
@@ -0,0 +1,2 @@
+pub mod test_manifest;
+pub mod test_thread_name;
@@ -0,0 +1,58 @@
+mod manifest {
+    use network_initializer::parsed_nodes::NodeType;
+    use network_initializer::{DroneType, NetworkInitializer};
+    use packet_forge::ClientType;
+
+    const TOPOLOGY: &str = "initialization_files/test_files/topology/diamond.toml";
+
+    #[test]
+    fn test_planned_manifest() {
+        let net_init = NetworkInitializer::new(Some(TOPOLOGY)).unwrap();
+        let manifest = net_init.plan_manifest(
+            Some(vec![DroneType::RustRoveri, DroneType::DrOnes]),
+            Some(vec![ClientType::Video]),
+        );
+
+        assert_eq!(manifest.seed, net_init.get_seed());
+        assert_eq!(manifest.nodes.len(), 6);
+        assert_eq!(manifest.of_kind(NodeType::Drone).count(), 4);
+
+        // Round robin follows the order of the available implementations
+        let implementations: Vec<&str> = manifest
+            .of_kind(NodeType::Drone)
+            .map(|entry| entry.implementation.as_str())
+            .collect();
+        assert_eq!(
+            implementations,
+            vec!["DrOnes", "RustRoveri", "DrOnes", "RustRoveri"]
+        );
+
+        let drone = manifest.get(4).unwrap();
+        assert_eq!(drone.kind, NodeType::Drone);
+        assert_eq!(drone.pdr, Some(0.5));
+        assert_eq!(drone.neighbours, vec![3, 20]);
+        assert_eq!(drone.asset_path, None);
+
+        let client = manifest.get(10).unwrap();
+        assert_eq!(client.implementation, "ClientVideo");
+        assert_eq!(client.pdr, None);
+        assert_eq!(client.neighbours, vec![1, 2]);
+        assert_eq!(
+            client.asset_path.as_deref(),
+            Some("./initialization_files/client_video/client1")
+        );
+
+        let server = manifest.get(20).unwrap();
+        assert_eq!(server.implementation, "Server");
+        assert_eq!(
+            server.asset_path.as_deref(),
+            Some("./initialization_files/server/server1")
+        );
+    }
+
+    #[test]
+    fn test_no_manifest_before_run() {
+        let net_init = NetworkInitializer::new(Some(TOPOLOGY)).unwrap();
+        assert_eq!(net_init.get_manifest(), None);
+    }
+}
@@ -0,0 +1,35 @@
+mod manifest {
+    use network_initializer::manifest::NodeManifestEntry;
+    use network_initializer::parsed_nodes::NodeType;
+
+    fn entry(id: u8, kind: NodeType, implementation: &str) -> NodeManifestEntry {
+        NodeManifestEntry {
+            id,
+            kind,
+            implementation: implementation.to_string(),
+            pdr: None,
+            neighbours: Vec::new(),
+            asset_path: None,
+        }
+    }
+
+    #[test]
+    fn test_thread_names() {
+        assert_eq!(
+            entry(3, NodeType::Drone, "RustRoveri").thread_name(),
+            "drone-3-RustRoveri"
+        );
+        assert_eq!(
+            entry(20, NodeType::Client, "ClientVideo").thread_name(),
+            "client-20-video"
+        );
+        assert_eq!(
+            entry(21, NodeType::Client, "ClientAudio").thread_name(),
+            "client-21-audio"
+        );
+        assert_eq!(
+            entry(30, NodeType::Server, "Server").thread_name(),
+            "server-30"
+        );
+    }
+}
@@ -1,3 +1,7 @@
 pub mod test_bidirectionality;
+pub mod test_channel_capacity;
+pub mod test_links;
+pub mod test_log_level;
 pub mod test_node_connection;
+pub mod test_set_path;
 pub mod test_unique_ids;
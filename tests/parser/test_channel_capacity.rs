@@ -0,0 +1,26 @@
+mod parser {
+    use network_initializer::errors::ConfigError;
+    use network_initializer::NetworkInitializer;
+
+    #[test]
+    fn test_ok() {
+        let path = "initialization_files/test_files/channel_capacity/ok.toml";
+        let config = NetworkInitializer::new(Some(path));
+
+        assert!(config.is_ok(), "{}", config.err().unwrap());
+
+        let config = config.unwrap();
+        let (drones, _, _) = config.get_nodes();
+        assert_eq!(drones[0].channel_capacity, Some(16));
+        assert_eq!(drones[1].channel_capacity, None);
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let path = "initialization_files/test_files/channel_capacity/zero_capacity.toml";
+        let config = NetworkInitializer::new(Some(path));
+
+        assert!(config.is_err());
+        assert_eq!(config.err().unwrap(), ConfigError::ZeroChannelCapacity);
+    }
+}
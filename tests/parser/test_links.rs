@@ -0,0 +1,30 @@
+mod parser {
+    use network_initializer::errors::ConfigError;
+    use network_initializer::NetworkInitializer;
+
+    #[test]
+    fn test_ok() {
+        let path = "initialization_files/test_files/links/ok.toml";
+        let config = NetworkInitializer::new(Some(path));
+
+        assert!(config.is_ok(), "{}", config.err().unwrap());
+    }
+
+    #[test]
+    fn test_link_without_connection() {
+        let path = "initialization_files/test_files/links/err_link.toml";
+        let config = NetworkInitializer::new(Some(path));
+
+        assert!(config.is_err());
+        assert_eq!(config.err().unwrap(), ConfigError::InvalidLink(5, 6));
+    }
+
+    #[test]
+    fn test_invalid_loss() {
+        let path = "initialization_files/test_files/links/err_loss.toml";
+        let config = NetworkInitializer::new(Some(path));
+
+        assert!(config.is_err());
+        assert_eq!(config.err().unwrap(), ConfigError::InvalidLink(1, 2));
+    }
+}
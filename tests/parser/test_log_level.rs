@@ -0,0 +1,71 @@
+mod parser {
+    use network_initializer::apply_log_level;
+    use network_initializer::errors::ConfigError;
+    use network_initializer::log_level::LogLevel;
+    use network_initializer::NetworkInitializer;
+
+    /// Records the levels enabled on it, as a client or a server would
+    #[derive(Default)]
+    struct Node {
+        enabled: Vec<&'static str>,
+    }
+
+    impl Node {
+        fn with_error(&mut self) {
+            self.enabled.push("error");
+        }
+        fn with_warning(&mut self) {
+            self.enabled.push("warning");
+        }
+        fn with_info(&mut self) {
+            self.enabled.push("info");
+        }
+        fn with_debug(&mut self) {
+            self.enabled.push("debug");
+        }
+    }
+
+    fn enabled(level: LogLevel) -> Vec<&'static str> {
+        let mut node = Node::default();
+        apply_log_level!(node, level);
+        node.enabled
+    }
+
+    #[test]
+    fn test_ok() {
+        let path = "initialization_files/test_files/log_level/ok.toml";
+        let config = NetworkInitializer::new(Some(path));
+
+        assert!(config.is_ok(), "{}", config.err().unwrap());
+
+        let config = config.unwrap();
+        let (_, clients, servers) = config.get_nodes();
+        assert_eq!(clients[0].log_level, Some(LogLevel::Debug));
+        assert_eq!(servers[0].log_level, None);
+    }
+
+    #[test]
+    fn test_invalid_level() {
+        let path = "initialization_files/test_files/log_level/err_level.toml";
+        let config = NetworkInitializer::new(Some(path));
+
+        assert!(config.is_err());
+        assert_eq!(
+            config.err().unwrap(),
+            ConfigError::ParseError(path.to_string())
+        );
+    }
+
+    #[test]
+    fn test_enabled_levels() {
+        assert!(enabled(LogLevel::Off).is_empty());
+        assert_eq!(enabled(LogLevel::Error), vec!["error"]);
+        // The default only prints errors and infos, warnings are opt-in
+        assert_eq!(enabled(LogLevel::default()), vec!["error", "info"]);
+        assert_eq!(enabled(LogLevel::Warning), vec!["error", "info", "warning"]);
+        assert_eq!(
+            enabled(LogLevel::Debug),
+            vec!["error", "info", "warning", "debug"]
+        );
+    }
+}
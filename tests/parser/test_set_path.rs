@@ -0,0 +1,94 @@
+mod parser {
+    use std::iter;
+    use std::time::Duration;
+
+    use network_initializer::capture::read_capture;
+    use network_initializer::errors::ConfigError;
+    use network_initializer::simulation_event::{LinkDropCause, SimulationEvent};
+    use network_initializer::NetworkInitializer;
+
+    const DIAMOND: &str = "initialization_files/test_files/topology/diamond.toml";
+    const LINKS: &str = "initialization_files/test_files/links/ok.toml";
+    const SEEDED: &str = "initialization_files/test_files/links/seeded.toml";
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn test_channels_rebuilt() {
+        let mut net_init = NetworkInitializer::new(Some(DIAMOND)).unwrap();
+        net_init.set_path(Some(LINKS)).unwrap();
+
+        let mut ids: Vec<_> = net_init.get_channels().into_keys().collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec![1, 2, 5, 6]);
+
+        let mut ids: Vec<_> = net_init.get_controller_senders().into_keys().collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec![1, 2, 5, 6]);
+    }
+
+    #[test]
+    fn test_link_loss() {
+        // The link from 6 to 1 loses 10% of the fragments, the diamond has no link properties
+        let mut net_init = NetworkInitializer::new(Some(DIAMOND)).unwrap();
+        net_init.set_path(Some(LINKS)).unwrap();
+        let events = net_init.get_simulation_recv();
+        let packet = read_capture("initialization_files/test_files/capture/session.jsonl")
+            .unwrap()
+            .remove(0)
+            .to_packet();
+
+        // Kept alive so that the link can deliver to node 1
+        let _channels = net_init.get_channels();
+        let sender = net_init.get_link_sender(6, 1).unwrap();
+        for _ in 0..200 {
+            sender.send(packet.clone()).unwrap();
+        }
+
+        let lost = SimulationEvent::LinkDropped {
+            from: 6,
+            to: 1,
+            session_id: 42,
+            cause: LinkDropCause::Loss,
+        };
+        assert!(iter::from_fn(|| events.recv_timeout(TIMEOUT).ok()).any(|event| event == lost));
+    }
+
+    /// Sends fragments from 6 to 1 and returns how many of them the link lost
+    fn losses(net_init: &mut NetworkInitializer) -> usize {
+        let events = net_init.get_simulation_recv();
+        let packet = read_capture("initialization_files/test_files/capture/session.jsonl")
+            .unwrap()
+            .remove(0)
+            .to_packet();
+
+        let channels = net_init.get_channels();
+        let sender = net_init.get_link_sender(6, 1).unwrap();
+        for _ in 0..100 {
+            sender.send(packet.clone()).unwrap();
+        }
+        while channels[&1].receiver.recv_timeout(TIMEOUT).is_ok() {}
+
+        events.try_iter().count()
+    }
+
+    #[test]
+    fn test_seed() {
+        let mut net_init = NetworkInitializer::new(Some(DIAMOND)).unwrap();
+        net_init.set_path(Some(SEEDED)).unwrap();
+        assert_eq!(net_init.get_seed(), 7);
+
+        let mut expected = NetworkInitializer::new(Some(SEEDED)).unwrap();
+        assert_eq!(losses(&mut net_init), losses(&mut expected));
+    }
+
+    #[test]
+    fn test_after_initialization() {
+        let mut net_init = NetworkInitializer::new(Some(DIAMOND)).unwrap();
+        let _ = net_init.get_channels();
+
+        assert_eq!(
+            net_init.set_path(Some(LINKS)).err(),
+            Some(ConfigError::AlreadyInitialized)
+        );
+    }
+}
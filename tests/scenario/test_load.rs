@@ -0,0 +1,30 @@
+mod scenario {
+    use network_initializer::errors::ScenarioError;
+    use network_initializer::simulation::scenario::{Scenario, ScenarioAction};
+
+    #[test]
+    fn test_load_sorted() {
+        let scenario = Scenario::load("initialization_files/test_files/scenario/ok.toml").unwrap();
+
+        let times: Vec<u64> = scenario.steps.iter().map(|step| step.at_ms).collect();
+        assert_eq!(times, vec![2000, 4000, 6000]);
+        assert_eq!(scenario.steps[0].action, ScenarioAction::Crash { node: 5 });
+        assert_eq!(
+            scenario.steps[1].action,
+            ScenarioAction::SetPdr { node: 3, pdr: 0.5 }
+        );
+        assert_eq!(
+            scenario.steps[2].action,
+            ScenarioAction::RemoveLink { a: 1, b: 2 }
+        );
+    }
+
+    #[test]
+    fn test_unknown_action() {
+        let path = "initialization_files/test_files/scenario/err_action.toml";
+        assert_eq!(
+            Scenario::load(path),
+            Err(ScenarioError::ParseError(path.to_string()))
+        );
+    }
+}
@@ -0,0 +1,92 @@
+mod supervision {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use crossbeam::channel::{Receiver, Sender};
+    use network_initializer::simulation::SimulationController;
+    use network_initializer::simulation_event::{ControllerEvent, SimulationEvent};
+    use network_initializer::{DroneType, NetworkInitializer, RestartPolicy};
+    use wg_internal::controller::{DroneCommand, DroneEvent};
+    use wg_internal::drone::Drone;
+    use wg_internal::network::NodeId;
+    use wg_internal::packet::Packet;
+
+    const TOPOLOGY: &str = "initialization_files/test_files/supervision/single_drone.toml";
+    const MESSAGE: &str = "stub drone panicked";
+
+    /// Drone panicking as soon as it runs
+    struct PanickingDrone;
+
+    impl Drone for PanickingDrone {
+        fn new(
+            _id: NodeId,
+            _controller_send: Sender<DroneEvent>,
+            _controller_recv: Receiver<DroneCommand>,
+            _packet_recv: Receiver<Packet>,
+            _packet_send: HashMap<NodeId, Sender<Packet>>,
+            _pdr: f32,
+        ) -> Self {
+            PanickingDrone
+        }
+
+        fn run(&mut self) {
+            panic!("{MESSAGE}");
+        }
+    }
+
+    fn panicked() -> SimulationEvent {
+        SimulationEvent::NodePanicked {
+            id: 1,
+            message: MESSAGE.to_string(),
+        }
+    }
+
+    fn initializer(policy: RestartPolicy) -> NetworkInitializer {
+        let mut net_init = NetworkInitializer::new(Some(TOPOLOGY)).unwrap();
+        net_init.register_drone::<PanickingDrone>("panicking");
+        net_init.set_restart_policy(policy);
+        net_init
+    }
+
+    #[test]
+    fn test_restart_until_limit() {
+        let mut net_init = initializer(RestartPolicy::OnPanic { max_restarts: 2 });
+        let events = net_init.get_simulation_recv();
+        let _ = net_init.get_controller_recv();
+        let _ = net_init.get_controller_senders();
+        let _ = net_init.get_channels();
+
+        net_init
+            .run_simulation(Some(vec![DroneType::Custom("panicking")]), None)
+            .unwrap();
+
+        let events: Vec<SimulationEvent> = events.try_iter().collect();
+        assert_eq!(
+            events,
+            vec![
+                panicked(),
+                SimulationEvent::NodeRestarted { id: 1, restarts: 1 },
+                panicked(),
+                SimulationEvent::NodeRestarted { id: 1, restarts: 2 },
+                panicked(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_panic_on_controller_stream() {
+        let mut net_init = initializer(RestartPolicy::Never);
+        let controller = SimulationController::new(&mut net_init);
+        let _handle = controller.spawn();
+
+        net_init
+            .run_simulation(Some(vec![DroneType::Custom("panicking")]), None)
+            .unwrap();
+
+        let event = controller.events().recv_timeout(Duration::from_secs(1));
+        assert!(
+            matches!(event, Ok(ControllerEvent::Simulation(ref e)) if *e == panicked()),
+            "{event:?}"
+        );
+    }
+}
@@ -0,0 +1,95 @@
+mod topology_generator {
+    use network_initializer::errors::ConfigError;
+    use network_initializer::parser::Parser;
+    use network_initializer::topology_generator::{generate_topology, TopologyConfig};
+    use network_initializer::{AssignmentStrategy, DroneType, NetworkInitializer};
+
+    fn describe(parser: &Parser) -> String {
+        format!(
+            "{:?} {:?} {:?}",
+            parser.drones, parser.clients, parser.servers
+        )
+    }
+
+    #[test]
+    fn test_same_seed_same_topology() {
+        let config = TopologyConfig::default();
+        let first = generate_topology(&config, 7).unwrap();
+        let second = generate_topology(&config, 7).unwrap();
+        let other = generate_topology(&config, 8).unwrap();
+
+        assert_eq!(describe(&first), describe(&second));
+        assert_ne!(describe(&first), describe(&other));
+        assert_eq!(first.seed, Some(7));
+    }
+
+    #[test]
+    fn test_generated_shape() {
+        let config = TopologyConfig {
+            drones: 6,
+            clients: 3,
+            servers: 2,
+            extra_links: 2,
+            pdr_range: (0.1, 0.2),
+        };
+        let parser = generate_topology(&config, 42).unwrap();
+
+        let drone_ids: Vec<u8> = parser.drones.iter().map(|d| d.id).collect();
+        assert_eq!(drone_ids, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(parser.clients.len(), 3);
+        assert_eq!(parser.servers.len(), 2);
+
+        // Spanning tree plus the extra links
+        let drone_links: usize = parser
+            .drones
+            .iter()
+            .flat_map(|d| &d.connected_drone_ids)
+            .filter(|id| drone_ids.contains(id))
+            .count();
+        assert_eq!(drone_links, 2 * (5 + 2));
+
+        for drone in &parser.drones {
+            assert!((0.1..=0.2).contains(&drone.pdr));
+        }
+        for client in &parser.clients {
+            assert!((1..=2).contains(&client.connected_drone_ids.len()));
+        }
+        for server in &parser.servers {
+            assert_eq!(server.connected_drone_ids.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let config = TopologyConfig {
+            drones: 1,
+            ..TopologyConfig::default()
+        };
+        assert!(matches!(
+            generate_topology(&config, 1),
+            Err(ConfigError::InvalidTopologyConfig)
+        ));
+    }
+
+    #[test]
+    fn test_seeded_assignment() {
+        let plan = |seed| {
+            let mut net_init =
+                NetworkInitializer::generate(&TopologyConfig::default(), seed).unwrap();
+            net_init.set_assignment_strategy(AssignmentStrategy::Random);
+            net_init.plan_manifest(
+                Some(vec![
+                    DroneType::RustRoveri,
+                    DroneType::DrOnes,
+                    DroneType::RustyDrone,
+                ]),
+                None,
+            )
+        };
+
+        let manifest = plan(3);
+        assert_eq!(manifest.seed, 3);
+        assert_eq!(manifest.nodes.len(), 14);
+        assert_eq!(manifest, plan(3));
+    }
+}